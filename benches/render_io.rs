@@ -0,0 +1,36 @@
+//! Time `render` into writers that don't buffer, where every `write` would otherwise be a call
+//! into the writer.
+//!
+//! Run with `cargo bench --bench render_io`.
+
+extern crate text_tables;
+
+use std::fs::File;
+use std::io;
+use std::time::Instant;
+
+const ITERATIONS: u32 = 1000;
+
+/// Run `f` repeatedly, printing the average time it took.
+fn bench<F: FnMut()>(name: &str, mut f: F) {
+    let start = Instant::now();
+    for _ in 0..ITERATIONS {
+        f();
+    }
+    let elapsed = start.elapsed() / ITERATIONS;
+    println!("{:<16} {:>10?}", name, elapsed);
+}
+
+fn main() {
+    let data = (0..100)
+        .map(|row| (0..10).map(|col| row * col).collect())
+        .collect::<Vec<Vec<u32>>>();
+    let mut sink = io::sink();
+    bench("io::sink", || {
+        text_tables::render(&mut sink, &data).unwrap();
+    });
+    let mut null = File::create("/dev/null").unwrap();
+    bench("/dev/null", || {
+        text_tables::render(&mut null, &data).unwrap();
+    });
+}
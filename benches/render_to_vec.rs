@@ -0,0 +1,62 @@
+//! Compare `render_to_vec`, which sizes its buffer up front, with rendering into an empty `Vec`.
+//!
+//! Run with `cargo bench --bench render_to_vec`.
+
+extern crate text_tables;
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Instant;
+
+/// Counts every allocation and reallocation, so the two ways of rendering can be compared.
+struct CountingAlloc;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAlloc = CountingAlloc;
+
+const ITERATIONS: u32 = 100;
+
+/// Run `f` repeatedly, printing the average time and number of allocations it took.
+fn bench<F: FnMut() -> Vec<u8>>(name: &str, mut f: F) {
+    let allocations = ALLOCATIONS.load(Ordering::Relaxed);
+    let start = Instant::now();
+    for _ in 0..ITERATIONS {
+        f();
+    }
+    let elapsed = start.elapsed() / ITERATIONS;
+    let allocations = (ALLOCATIONS.load(Ordering::Relaxed) - allocations) / ITERATIONS as usize;
+    println!(
+        "{:<16} {:>10?} {:>8} allocations",
+        name, elapsed, allocations
+    );
+}
+
+fn main() {
+    let data = (0..200)
+        .map(|row| (0..20).map(|col| row * col).collect())
+        .collect::<Vec<Vec<u32>>>();
+    bench("render", || {
+        let mut out = Vec::new();
+        text_tables::render(&mut out, &data).unwrap();
+        out
+    });
+    bench("render_to_vec", || text_tables::render_to_vec(&data));
+}
@@ -0,0 +1,304 @@
+//! Cells that can hold any displayable value, so columns can have different types, or nothing.
+
+use std::{
+    fmt::{self, Display},
+    io,
+};
+
+use width::str_width;
+use {render, TableError};
+
+/// A boxed displayable value, for tables whose columns hold different types.
+///
+/// All the cells in a table need to be the same type, so to mix, say, names and numbers, wrap
+/// each value in a `Cell`.
+///
+/// ```
+/// use text_tables::Cell;
+///
+/// let data = vec![
+///     vec![Cell::from("half"), Cell::from(0.5)],
+///     vec![Cell::from("answer"), Cell::from(42)],
+/// ];
+/// let mut out = Vec::new();
+/// text_tables::render(&mut out, data).unwrap();
+/// ```
+pub struct Cell(Box<dyn Display>);
+
+impl Cell {
+    /// Wrap any displayable value in a cell.
+    pub fn new<T: Display + 'static>(value: T) -> Self {
+        Cell(Box::new(value))
+    }
+}
+
+impl Display for Cell {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl fmt::Debug for Cell {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("Cell").field(&self.0.to_string()).finish()
+    }
+}
+
+// A blanket `impl<T: Display> From<T>` would overlap with `From<Cell> for Cell`, so cover the
+// common types instead. Anything else can use `Cell::new`.
+macro_rules! impl_from {
+    ($($ty:ty),*) => {
+        $(
+            impl From<$ty> for Cell {
+                fn from(value: $ty) -> Self {
+                    Cell::new(value)
+                }
+            }
+        )*
+    };
+}
+
+impl_from!(
+    &'static str,
+    String,
+    char,
+    bool,
+    i8,
+    i16,
+    i32,
+    i64,
+    i128,
+    isize,
+    u8,
+    u16,
+    u32,
+    u64,
+    u128,
+    usize,
+    f32,
+    f64
+);
+
+/// A cell that might be empty, displayed as nothing when it is `None`.
+///
+/// ```
+/// use text_tables::OptCell;
+///
+/// assert_eq!(OptCell(Some(3)).to_string(), "3");
+/// assert_eq!(OptCell::<u8>(None).to_string(), "");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct OptCell<C: Display>(pub Option<C>);
+
+impl<C: Display> Display for OptCell<C> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.0 {
+            Some(ref value) => value.fmt(f),
+            None => Ok(()),
+        }
+    }
+}
+
+/// A link that can be clicked in terminals that support OSC 8 hyperlinks.
+///
+/// Only the label is displayed, so only the label counts towards the width of the cell.
+/// Terminals without support just show the label.
+///
+/// ```
+/// use text_tables::HyperLink;
+///
+/// let link = HyperLink {
+///     url: "https://crates.io".to_string(),
+///     label: "crates".to_string(),
+/// };
+/// assert_eq!(link.to_string(), "\x1b]8;;https://crates.io\x1b\\crates\x1b]8;;\x1b\\");
+/// assert_eq!(link.visual_width(), 6);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct HyperLink {
+    /// Where the link goes.
+    pub url: String,
+    /// The text that is displayed.
+    pub label: String,
+}
+
+impl HyperLink {
+    /// Get the width the link takes up in a terminal, which is the width of its label.
+    pub fn visual_width(&self) -> usize {
+        str_width(&self.label)
+    }
+}
+
+impl Display for HyperLink {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "\x1b]8;;{}\x1b\\{}\x1b]8;;\x1b\\", self.url, self.label)
+    }
+}
+
+/// Render a table of cells that might be missing, leaving the `None` cells empty
+///
+/// ```
+/// let data = vec![vec![Some("a"), None], vec![None, Some("b")]];
+/// let mut out = Vec::new();
+/// text_tables::render_option(&mut out, &data).unwrap();
+/// assert_eq!(::std::str::from_utf8(&out).unwrap(), "\
+/// +---+---+
+/// | a |   |
+/// +---+---+
+/// |   | b |
+/// +---+---+
+/// ");
+/// ```
+///
+/// # Errors
+///
+/// Fails if all rows are not the same length, or if writing fails.
+pub fn render_option<W, T, R, C>(writer: &mut W, data: T) -> Result<(), TableError>
+where
+    W: io::Write,
+    T: AsRef<[R]>,
+    R: AsRef<[Option<C>]>,
+    C: Display,
+{
+    let rows = data
+        .as_ref()
+        .iter()
+        .map(|row| {
+            row.as_ref()
+                .iter()
+                .map(|cell| OptCell(cell.as_ref()))
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+    render(writer, rows)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{render_option, Cell, HyperLink};
+
+    #[test]
+    fn mixed_types() {
+        let data = vec![
+            vec![Cell::from("name"), Cell::from("count"), Cell::from("ratio")],
+            vec![Cell::from("a"), Cell::from(-3i64), Cell::from(0.5)],
+            vec![
+                Cell::from("b".to_string()),
+                Cell::from(12i64),
+                Cell::from(1.25),
+            ],
+        ];
+        assert_eq!(
+            ::render_to_string(&data),
+            "\
++------+-------+-------+
+| name | count | ratio |
++------+-------+-------+
+| a    | -3    | 0.5   |
++------+-------+-------+
+| b    | 12    | 1.25  |
++------+-------+-------+
+"
+        );
+    }
+
+    fn options(data: &[Vec<Option<u32>>]) -> String {
+        let mut out = Vec::new();
+        render_option(&mut out, data).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn none_cells() {
+        assert_eq!(
+            options(&[vec![Some(1), Some(2)], vec![Some(3), None]]),
+            "+---+---+\n| 1 | 2 |\n+---+---+\n| 3 |   |\n+---+---+\n"
+        );
+        assert_eq!(
+            options(&[vec![None, None], vec![Some(10), Some(2)]]),
+            "+----+---+\n|    |   |\n+----+---+\n| 10 | 2 |\n+----+---+\n"
+        );
+        assert_eq!(
+            options(&[vec![Some(100)], vec![None], vec![Some(7)]]),
+            "+-----+\n| 100 |\n+-----+\n|     |\n+-----+\n| 7   |\n+-----+\n"
+        );
+    }
+
+    #[test]
+    fn hyperlinks() {
+        let link = |label: &str| {
+            Cell::new(HyperLink {
+                url: "https://example.com/a/long/path".to_string(),
+                label: label.to_string(),
+            })
+        };
+        let data = vec![
+            vec![Cell::from("docs"), link("here")],
+            vec![Cell::from("a longer name"), Cell::from(1)],
+        ];
+        let rendered = ::render_to_string(&data);
+        let plain = ::width::strip_ansi(&rendered);
+        assert_eq!(
+            plain,
+            "\
++---------------+------+
+| docs          | here |
++---------------+------+
+| a longer name | 1    |
++---------------+------+
+"
+        );
+        assert!(rendered.contains("\x1b]8;;https://example.com/a/long/path\x1b\\here"));
+
+        let data = vec![vec![link("a very long label")], vec![Cell::from("b")]];
+        assert_eq!(
+            ::width::strip_ansi(&::render_to_string(&data)),
+            "\
++-------------------+
+| a very long label |
++-------------------+
+| b                 |
++-------------------+
+"
+        );
+    }
+
+    #[test]
+    fn hyperlinks_with_max_width() {
+        use {Options, Overflow};
+
+        let data = vec![vec![Cell::new(HyperLink {
+            url: "https://example.com".to_string(),
+            label: "a link".to_string(),
+        })]];
+        let render = |overflow| {
+            let options = Options {
+                max_column_widths: vec![Some(3)],
+                overflow,
+                ..Options::default()
+            };
+            let mut out = Vec::new();
+            ::render_with_options(&mut out, &data, &options).unwrap();
+            String::from_utf8(out).unwrap()
+        };
+        assert_eq!(
+            render(Overflow::Truncate),
+            "\
++-----+
+| \x1b]8;;https://example.com\x1b\\a \x1b]8;;\x1b\\… |
++-----+
+"
+        );
+        assert_eq!(
+            render(Overflow::Wrap),
+            "\
++-----+
+| \x1b]8;;https://example.com\x1b\\a\x1b]8;;\x1b\\   |
+| \x1b]8;;https://example.com\x1b\\lin\x1b]8;;\x1b\\ |
+| \x1b]8;;https://example.com\x1b\\k\x1b]8;;\x1b\\   |
++-----+
+"
+        );
+    }
+}
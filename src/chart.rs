@@ -0,0 +1,142 @@
+//! Small charts and numbers drawn with text, for use as cells.
+
+use std::fmt::{self, Display, Write};
+
+/// The block characters a sparkline is drawn with, from lowest to highest.
+const BLOCKS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+/// A tiny bar chart of a series of values, drawn with one block character per value.
+///
+/// The smallest value is drawn as `▁` and the largest as `█`, with the rest spread evenly
+/// between them. If all the values are the same they are drawn as `▁`. Values that are not
+/// numbers (`NaN`) are drawn as a space.
+///
+/// ```
+/// use text_tables::Sparkline;
+///
+/// assert_eq!(Sparkline(vec![1.0, 5.0, 3.0, 8.0]).to_string(), "▁▅▃█");
+/// ```
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Sparkline(pub Vec<f64>);
+
+impl Display for Sparkline {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let values = self.0.iter().filter(|value| !value.is_nan());
+        let min = values.clone().cloned().fold(f64::INFINITY, f64::min);
+        let max = values.cloned().fold(f64::NEG_INFINITY, f64::max);
+        for &value in &self.0 {
+            if value.is_nan() {
+                f.write_char(' ')?;
+                continue;
+            }
+            let level = if max > min {
+                ((value - min) / (max - min) * (BLOCKS.len() - 1) as f64).round() as usize
+            } else {
+                0
+            };
+            f.write_char(BLOCKS[level])?;
+        }
+        Ok(())
+    }
+}
+
+/// A percentage, displayed with one decimal place and a `%` sign.
+///
+/// The value is already a percentage, so `PercentCell(12.5)` is displayed as `12.5%`. Values
+/// outside 0 to 100 are clamped.
+///
+/// ```
+/// use text_tables::PercentCell;
+///
+/// assert_eq!(PercentCell(12.345).to_string(), "12.3%");
+/// assert_eq!(PercentCell(250.0).to_string(), "100.0%");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct PercentCell(pub f64);
+
+impl Display for PercentCell {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:.1}%", self.0.clamp(0.0, 100.0))
+    }
+}
+
+/// A bar some number of characters wide, filled in from the left to show a fraction.
+///
+/// The first value is the fraction, from 0 to 1, and the second is the width of the bar. Filled
+/// characters are drawn as `█` and the rest as `░`. Fractions outside 0 to 1 are clamped, and
+/// `NaN` draws an empty bar.
+///
+/// ```
+/// use text_tables::BarCell;
+///
+/// assert_eq!(BarCell(0.75, 4).to_string(), "███░");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct BarCell(pub f64, pub usize);
+
+impl Display for BarCell {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let BarCell(fraction, width) = *self;
+        let filled = if fraction.is_nan() {
+            0
+        } else {
+            (fraction.clamp(0.0, 1.0) * width as f64).round() as usize
+        };
+        for idx in 0..width {
+            f.write_char(if idx < filled { '█' } else { '░' })?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{BarCell, PercentCell, Sparkline};
+
+    #[test]
+    fn sparkline() {
+        let line = |values: &[f64]| Sparkline(values.to_vec()).to_string();
+        // 0 is the min, 14 the max and 7 the mid-point, which rounds up.
+        assert_eq!(line(&[0.0, 14.0, 7.0, 2.0, 12.0]), "▁█▅▂▇");
+        assert_eq!(line(&[-1.0, -3.0]), "█▁");
+        assert_eq!(line(&[4.0, 4.0]), "▁▁");
+        assert_eq!(line(&[1.0, f64::NAN, 2.0]), "▁ █");
+        assert_eq!(line(&[]), "");
+
+        let data = vec![vec![
+            "load".to_string(),
+            Sparkline(vec![1.0, 2.0]).to_string(),
+        ]];
+        assert_eq!(
+            ::render_to_string(&data),
+            "+------+----+\n| load | ▁█ |\n+------+----+\n"
+        );
+    }
+
+    #[test]
+    fn percent() {
+        assert_eq!(PercentCell(0.0).to_string(), "0.0%");
+        assert_eq!(PercentCell(100.0).to_string(), "100.0%");
+        assert_eq!(PercentCell(-12.5).to_string(), "0.0%");
+        assert_eq!(PercentCell(250.0).to_string(), "100.0%");
+    }
+
+    #[test]
+    fn bar() {
+        assert_eq!(BarCell(0.5, 10).to_string(), "█████░░░░░");
+        assert_eq!(BarCell(0.0, 3).to_string(), "░░░");
+        assert_eq!(BarCell(1.0, 3).to_string(), "███");
+        assert_eq!(BarCell(2.0, 2).to_string(), "██");
+        assert_eq!(BarCell(f64::NAN, 2).to_string(), "░░");
+        assert_eq!(BarCell(0.5, 0).to_string(), "");
+
+        let data = vec![vec![
+            PercentCell(50.0).to_string(),
+            BarCell(0.5, 4).to_string(),
+        ]];
+        assert_eq!(
+            ::render_to_string(&data),
+            "+-------+------+\n| 50.0% | ██░░ |\n+-------+------+\n"
+        );
+    }
+}
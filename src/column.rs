@@ -0,0 +1,227 @@
+//! Describing a column up front, including how to format its cells.
+
+use std::{any::Any, fmt, rc::Rc};
+
+use Alignment;
+
+/// Turns a cell added with `Table::add_row_any` into text.
+pub type CellFormatter = Box<dyn Fn(&dyn Any) -> String>;
+
+/// A description of a column, for use with `Table::add_column_def`.
+///
+/// The formatter is given each cell added with `Table::add_row_any`, and can downcast it to the
+/// type it expects.
+///
+/// ```
+/// use text_tables::{Alignment, ColumnDef, Table};
+///
+/// let mut table = Table::new();
+/// table.add_column_def(ColumnDef::new("Item"));
+/// table.add_column_def(ColumnDef {
+///     alignment: Alignment::Right,
+///     formatter: Some(Box::new(|value| match value.downcast_ref::<f64>() {
+///         Some(price) => format!("£{:.2}", price),
+///         None => String::new(),
+///     })),
+///     ..ColumnDef::new("Price")
+/// });
+/// table.add_row_any(vec![Box::new("Tea"), Box::new(1.5f64)]);
+/// ```
+pub struct ColumnDef {
+    /// The column's header.
+    pub header: String,
+    /// How the column's cells are aligned.
+    pub alignment: Alignment,
+    /// Turns a cell into text. Without one, the cell is displayed as long as it is a string,
+    /// number, `bool` or `char`.
+    pub formatter: Option<CellFormatter>,
+    /// Makes the column exactly this wide, truncating cells that are wider and padding those
+    /// that are narrower. Otherwise the column is as wide as its content.
+    pub fixed_width: Option<usize>,
+}
+
+impl ColumnDef {
+    /// Describe a left-aligned column with no special formatting.
+    pub fn new<S: Into<String>>(header: S) -> Self {
+        ColumnDef {
+            header: header.into(),
+            alignment: Alignment::default(),
+            formatter: None,
+            fixed_width: None,
+        }
+    }
+}
+
+impl fmt::Debug for ColumnDef {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ColumnDef")
+            .field("header", &self.header)
+            .field("alignment", &self.alignment)
+            .field("formatter", &self.formatter.as_ref().map(|_| "Fn"))
+            .field("fixed_width", &self.fixed_width)
+            .finish()
+    }
+}
+
+/// What a table remembers about a column defined with a `ColumnDef`.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub(crate) struct ColumnFormat {
+    pub(crate) alignment: Alignment,
+    pub(crate) formatter: Option<Formatter>,
+    pub(crate) fixed_width: Option<usize>,
+}
+
+impl ColumnFormat {
+    /// Turn a cell from this column into text.
+    pub(crate) fn format(&self, value: &dyn Any) -> String {
+        match self.formatter {
+            Some(ref formatter) => (formatter.0)(value),
+            None => display_any(value),
+        }
+    }
+}
+
+impl From<ColumnDef> for ColumnFormat {
+    fn from(def: ColumnDef) -> Self {
+        ColumnFormat {
+            alignment: def.alignment,
+            fixed_width: def.fixed_width,
+            formatter: def
+                .formatter
+                .map(|formatter| Formatter(Rc::from(formatter))),
+        }
+    }
+}
+
+/// A shared cell formatter, so tables can still be cloned and compared.
+///
+/// Two formatters are only equal if they are the same closure.
+#[derive(Clone)]
+pub(crate) struct Formatter(Rc<FormatFn>);
+
+type FormatFn = dyn Fn(&dyn Any) -> String;
+
+impl PartialEq for Formatter {
+    fn eq(&self, other: &Formatter) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Eq for Formatter {}
+
+impl fmt::Debug for Formatter {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("Formatter")
+    }
+}
+
+/// Display a value of one of the standard types, or nothing if it is some other type.
+pub(crate) fn display_any(value: &dyn Any) -> String {
+    macro_rules! try_display {
+        ($($ty:ty),*) => {
+            $(
+                if let Some(value) = value.downcast_ref::<$ty>() {
+                    return value.to_string();
+                }
+            )*
+        };
+    }
+    try_display!(
+        &'static str,
+        String,
+        char,
+        bool,
+        i8,
+        i16,
+        i32,
+        i64,
+        i128,
+        isize,
+        u8,
+        u16,
+        u32,
+        u64,
+        u128,
+        usize,
+        f32,
+        f64
+    );
+    String::new()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::any::Any;
+
+    use super::ColumnDef;
+    use {Alignment, Table};
+
+    /// Format a number with two decimal places and commas between the thousands.
+    fn thousands(value: &dyn Any) -> String {
+        let value = match value.downcast_ref::<f64>() {
+            Some(value) => format!("{:.2}", value),
+            None => return String::new(),
+        };
+        let (whole, fraction) = value.split_at(value.find('.').unwrap());
+        let mut out = String::new();
+        for (idx, c) in whole.chars().enumerate() {
+            if idx > 0 && (whole.len() - idx) % 3 == 0 {
+                out.push(',');
+            }
+            out.push(c);
+        }
+        out + fraction
+    }
+
+    #[test]
+    fn formatter() {
+        let mut table = Table::new();
+        table
+            .add_column_def(ColumnDef::new("name"))
+            .add_column_def(ColumnDef {
+                alignment: Alignment::Right,
+                formatter: Some(Box::new(thousands)),
+                ..ColumnDef::new("total")
+            })
+            .add_row_any(vec![Box::new("a"), Box::new(1234.56f64)])
+            .add_row_any(vec![Box::new(7), Box::new(5.0f64)]);
+        assert_eq!(
+            table.to_string(),
+            "\
++------+----------+
+| name |    total |
++======+==========+
+| a    | 1,234.56 |
++------+----------+
+| 7    |     5.00 |
++------+----------+
+"
+        );
+    }
+
+    #[test]
+    fn fixed_width() {
+        let render = |fixed_width| {
+            let mut table = Table::new();
+            table
+                .add_column_def(ColumnDef {
+                    fixed_width: Some(fixed_width),
+                    ..ColumnDef::new("status")
+                })
+                .add_row_any(vec![Box::new("ok")]);
+            table.to_string()
+        };
+        assert_eq!(
+            render(4),
+            "+------+\n| sta… |\n+======+\n| ok   |\n+------+\n"
+        );
+        assert_eq!(
+            render(8),
+            "+----------+\n| status   |\n+==========+\n| ok       |\n+----------+\n"
+        );
+        assert_eq!(
+            render(6),
+            "+--------+\n| status |\n+========+\n| ok     |\n+--------+\n"
+        );
+    }
+}
@@ -0,0 +1,284 @@
+//! Rendering CSV text as a table.
+
+use std::io;
+
+use {Options, Table, TableError};
+
+/// Parse CSV text and render it as a table
+///
+/// If `has_header` is set, the first record is rendered as the header. Quoted fields can contain
+/// commas, line breaks and doubled quotes (`""`).
+///
+/// ```
+/// let mut out = Vec::new();
+/// text_tables::render_csv(&mut out, "name,age\nAlice,42\n", true).unwrap();
+/// assert_eq!(::std::str::from_utf8(&out).unwrap(), "\
+/// +-------+-----+
+/// | name  | age |
+/// +=======+=====+
+/// | Alice | 42  |
+/// +-------+-----+
+/// ");
+/// ```
+///
+/// # Errors
+///
+/// Fails with `TableError::CsvError` if a quote is never closed or all records are not the same
+/// length, or if writing fails.
+pub fn render_csv<W: io::Write>(
+    writer: &mut W,
+    csv_input: &str,
+    has_header: bool,
+) -> Result<(), TableError> {
+    render_delimited(writer, csv_input, ',', has_header)
+}
+
+impl Table {
+    /// Read CSV from `reader` into a table
+    ///
+    /// If `has_header` is set, the first record becomes the header. Fields are parsed the same way
+    /// as by `render_csv`.
+    ///
+    /// ```
+    /// use text_tables::Table;
+    ///
+    /// let table = Table::from_csv_reader("name,age\nAlice,42\n".as_bytes(), true).unwrap();
+    /// let mut expected = Table::new();
+    /// expected.add_header(["name", "age"]).add_row(["Alice", "42"]);
+    /// assert_eq!(table, expected);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Fails with `TableError::CsvError` if reading fails, the input is not valid UTF-8, a quote is
+    /// never closed or all records are not the same length.
+    pub fn from_csv_reader<R: io::Read>(
+        mut reader: R,
+        has_header: bool,
+    ) -> Result<Table, TableError> {
+        let mut input = Vec::new();
+        reader
+            .read_to_end(&mut input)
+            .map_err(|e| TableError::CsvError(e.to_string()))?;
+        let input = String::from_utf8(input).map_err(|e| TableError::CsvError(e.to_string()))?;
+        let mut records = parse_records(&input, ',')?.into_iter();
+        let mut table = Table::new();
+        if has_header {
+            if let Some(header) = records.next() {
+                table.add_header(header);
+            }
+        }
+        for record in records {
+            table.add_row(record);
+        }
+        Ok(table)
+    }
+}
+
+/// Parse text with fields separated by `delimiter` and render it as a table
+///
+/// Use `'\t'` for tab-separated values. Otherwise this is the same as `render_csv`.
+///
+/// # Errors
+///
+/// Fails with `TableError::CsvError` if a quote is never closed or all records are not the same
+/// length, or if writing fails.
+pub fn render_delimited<W: io::Write>(
+    writer: &mut W,
+    input: &str,
+    delimiter: char,
+    has_header: bool,
+) -> Result<(), TableError> {
+    let options = Options {
+        header_row: has_header,
+        ..Options::default()
+    };
+    parse_records(input, delimiter)?
+        .into_iter()
+        .collect::<Table>()
+        .render_with_options(writer, &options)
+}
+
+/// Split delimited text into records of fields.
+///
+/// Records end at `\n` or `\r\n`, except inside quotes. Blank lines are skipped.
+///
+/// Fails with `TableError::CsvError` if a quote is never closed, or if a record has a different
+/// number of fields to the first.
+pub(crate) fn parse_records(input: &str, delimiter: char) -> Result<Vec<Vec<String>>, TableError> {
+    let mut records = Vec::new();
+    let mut record = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    // whether nothing has been read since the last record ended.
+    let mut blank = true;
+    let mut chars = input.chars().peekable();
+    while let Some(c) = chars.next() {
+        if in_quotes {
+            match c {
+                '"' if chars.peek() == Some(&'"') => {
+                    chars.next();
+                    field.push('"');
+                }
+                '"' => in_quotes = false,
+                c => field.push(c),
+            }
+        } else if c == delimiter {
+            record.push(field.split_off(0));
+        } else if c == '"' && field.is_empty() {
+            in_quotes = true;
+        } else if c == '\n' || (c == '\r' && chars.peek() == Some(&'\n')) {
+            if c == '\r' {
+                chars.next();
+            }
+            if !blank {
+                record.push(field.split_off(0));
+                records.push(record.split_off(0));
+                blank = true;
+            }
+            continue;
+        } else {
+            field.push(c);
+        }
+        blank = false;
+    }
+    if in_quotes {
+        return Err(TableError::CsvError(format!(
+            "quote in record {} is never closed",
+            records.len() + 1
+        )));
+    }
+    // the last record might not end in a line break.
+    if !blank {
+        record.push(field);
+        records.push(record);
+    }
+    if let Some(first) = records.first() {
+        let expected = first.len();
+        if let Some(idx) = records.iter().position(|record| record.len() != expected) {
+            return Err(TableError::CsvError(format!(
+                "record {} has {} fields, but the first has {}",
+                idx + 1,
+                records[idx].len(),
+                expected
+            )));
+        }
+    }
+    Ok(records)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_records, render_delimited};
+    use {Table, TableError};
+
+    #[test]
+    fn parse() {
+        let tests = vec![
+            ("", vec![]),
+            ("a,b\nc,d\n", vec![vec!["a", "b"], vec!["c", "d"]]),
+            ("a,b\r\nc,d", vec![vec!["a", "b"], vec!["c", "d"]]),
+            ("a,,\n", vec![vec!["a", "", ""]]),
+            ("\"a,b\",c\n", vec![vec!["a,b", "c"]]),
+            ("\"say \"\"hi\"\"\"\n", vec![vec!["say \"hi\""]]),
+            ("\"two\nlines\",x\n", vec![vec!["two\nlines", "x"]]),
+            (
+                "a,b\n1,2\n\n3,4\n",
+                vec![vec!["a", "b"], vec!["1", "2"], vec!["3", "4"]],
+            ),
+            ("\r\n\na\r\n\r\n", vec![vec!["a"]]),
+            ("\"\"\n\"\"", vec![vec![""], vec![""]]),
+            ("\"a\n\nb\"\n", vec![vec!["a\n\nb"]]),
+        ];
+        for (input, records) in tests {
+            assert_eq!(parse_records(input, ',').unwrap(), records, "{:?}", input);
+        }
+    }
+
+    #[test]
+    fn malformed() {
+        let tests = vec![
+            ("\"unterminated", "quote in record 1 is never closed"),
+            ("a,b\n\"oops,1\n2,3\n", "quote in record 2 is never closed"),
+            ("a,b\n1\n", "record 2 has 1 fields, but the first has 2"),
+            ("a\n1,2", "record 2 has 2 fields, but the first has 1"),
+        ];
+        for (input, reason) in tests {
+            match Table::from_csv_reader(input.as_bytes(), true) {
+                Err(TableError::CsvError(ref e)) if e == reason => (),
+                other => panic!("unexpected result for {:?}: {:?}", input, other),
+            }
+        }
+        let mut out = Vec::new();
+        match render_delimited(&mut out, "a\tb\n\"1\t2\n", '\t', false) {
+            Err(TableError::CsvError(_)) => (),
+            other => panic!("unexpected result: {:?}", other),
+        }
+    }
+
+    fn render(input: &str, delimiter: char, has_header: bool) -> String {
+        let mut out = Vec::new();
+        render_delimited(&mut out, input, delimiter, has_header).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn render_csv() {
+        assert_eq!(
+            render("a,b\n1,2\n", ',', false),
+            "+---+---+\n| a | b |\n+---+---+\n| 1 | 2 |\n+---+---+\n"
+        );
+        assert_eq!(
+            render("a,b\n1,2\n\n3,4\n", ',', false),
+            "+---+---+\n| a | b |\n+---+---+\n| 1 | 2 |\n+---+---+\n| 3 | 4 |\n+---+---+\n"
+        );
+        assert_eq!(
+            render("name,where\nBob,\"Leeds, UK\"\n", ',', true),
+            "\
++------+-----------+
+| name | where     |
++======+===========+
+| Bob  | Leeds, UK |
++------+-----------+
+"
+        );
+    }
+
+    #[test]
+    fn render_tsv() {
+        assert_eq!(
+            render("a\tb,c\n1\t2\n", '\t', true),
+            "+---+-----+\n| a | b,c |\n+===+=====+\n| 1 | 2   |\n+---+-----+\n"
+        );
+    }
+
+    #[test]
+    fn from_csv_reader() {
+        let input = "name,\"where, exactly\"\nBob,\"Leeds, UK\"\n\"Ann \"\"A\"\"\",\"York\"\n";
+        let mut expected = Table::new();
+        expected
+            .add_header(["name", "where, exactly"])
+            .add_row(["Bob", "Leeds, UK"])
+            .add_row(["Ann \"A\"", "York"]);
+        assert_eq!(
+            Table::from_csv_reader(input.as_bytes(), true).unwrap(),
+            expected
+        );
+
+        let mut expected = Table::new();
+        expected.add_row(["a", "b"]).add_row(["1", "2"]);
+        assert_eq!(
+            Table::from_csv_reader("a,b\n1,2".as_bytes(), false).unwrap(),
+            expected
+        );
+
+        assert_eq!(
+            Table::from_csv_reader(&b""[..], true).unwrap(),
+            Table::new()
+        );
+        match Table::from_csv_reader(&b"a,\xff\n"[..], false) {
+            Err(TableError::CsvError(_)) => (),
+            other => panic!("unexpected result: {:?}", other),
+        }
+    }
+}
@@ -0,0 +1,79 @@
+//! Rendering tables lazily, when they are formatted.
+
+use std::{
+    fmt::{self, Display},
+    marker::PhantomData,
+};
+
+use render_fmt;
+
+/// Wrap table data so it is rendered when it is formatted
+///
+/// This is useful with logging macros and `format_args!`, which take `Display` values: nothing
+/// is rendered unless the message is actually formatted.
+///
+/// ```
+/// let data = [["a", "b"], ["c", "d"]];
+/// assert_eq!(
+///     format!("{}", text_tables::table_display(&data)),
+///     text_tables::render_to_string(&data)
+/// );
+/// ```
+pub fn table_display<T, R, C>(data: T) -> TableDisplay<T, R, C>
+where
+    T: AsRef<[R]>,
+    R: AsRef<[C]>,
+    C: Display,
+{
+    TableDisplay {
+        data,
+        marker: PhantomData,
+    }
+}
+
+/// Table data that is rendered with the default style when it is formatted.
+///
+/// Created by `table_display`. Formatting fails if all rows are not the same length.
+#[derive(Debug, Clone, Copy)]
+pub struct TableDisplay<T, R, C> {
+    data: T,
+    // only `T` is stored, so this shouldn't affect `Send` or `Sync`.
+    marker: PhantomData<fn() -> (R, C)>,
+}
+
+impl<T, R, C> Display for TableDisplay<T, R, C>
+where
+    T: AsRef<[R]>,
+    R: AsRef<[C]>,
+    C: Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        render_fmt(f, &self.data).map_err(|_| fmt::Error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::table_display;
+
+    #[test]
+    fn display() {
+        let data = vec![vec!["single", "line"], vec!["二", "lines"]];
+        assert_eq!(
+            format!("{}", table_display(&data)),
+            ::render_to_string(&data)
+        );
+        assert_eq!(format!("{}", table_display(Vec::<Vec<u8>>::new())), "");
+        assert!(::std::fmt::write(
+            &mut String::new(),
+            format_args!("{}", table_display(vec![vec![1, 2], vec![3]]))
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn send_sync() {
+        fn assert_send_sync<T: Send + Sync>(_: T) {}
+        assert_send_sync(table_display(vec![vec!["a".to_string()]]));
+    }
+}
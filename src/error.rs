@@ -0,0 +1,146 @@
+//! Errors that can occur while rendering or parsing a table.
+
+use std::{error, fmt, io};
+
+/// An error encountered while rendering a table.
+#[derive(Debug)]
+pub enum TableError {
+    /// A row had a different number of cells to the first row.
+    UnequalRowLengths {
+        /// The number of cells in the first row.
+        expected: usize,
+        /// The number of cells in the offending row.
+        got: usize,
+        /// The index of the offending row. A header row counts as row 0.
+        row_index: usize,
+    },
+    /// A row index was past the end of the table.
+    RowOutOfBounds {
+        /// The index that was given.
+        index: usize,
+        /// The number of rows in the table, not counting the header.
+        len: usize,
+    },
+    /// Two tables that were being combined had different numbers of columns.
+    IncompatibleColumnCounts {
+        /// The number of columns in the first table.
+        expected: usize,
+        /// The number of columns in the other table.
+        got: usize,
+    },
+    /// Two tables that were being combined had different numbers of rows.
+    IncompatibleRowCounts {
+        /// The number of rows in the first table.
+        expected: usize,
+        /// The number of rows in the other table.
+        got: usize,
+    },
+    /// A column index was past the last column of the table.
+    ColumnIndexOutOfRange {
+        /// The index that was given.
+        index: usize,
+        /// The number of columns in the table.
+        len: usize,
+    },
+    /// The table needed a header row, but didn't have one.
+    NoHeaderRow,
+    /// CSV input could not be read, for example because it was not valid UTF-8 or a quote was
+    /// never closed.
+    CsvError(String),
+    /// Writing to an `io::Write` failed.
+    Io(io::Error),
+    /// Writing to a `fmt::Write` failed.
+    Fmt(fmt::Error),
+}
+
+impl fmt::Display for TableError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            TableError::UnequalRowLengths {
+                expected,
+                got,
+                row_index,
+            } => write!(
+                f,
+                "rows must be the same length: row {} has {} cells, expected {}",
+                row_index, got, expected
+            ),
+            TableError::RowOutOfBounds { index, len } => write!(
+                f,
+                "row index out of bounds: the index is {} but there are {} rows",
+                index, len
+            ),
+            TableError::IncompatibleColumnCounts { expected, got } => write!(
+                f,
+                "tables must have the same number of columns: got {}, expected {}",
+                got, expected
+            ),
+            TableError::IncompatibleRowCounts { expected, got } => write!(
+                f,
+                "tables must have the same number of rows: got {}, expected {}",
+                got, expected
+            ),
+            TableError::ColumnIndexOutOfRange { index, len } => write!(
+                f,
+                "column index out of range: the index is {} but there are {} columns",
+                index, len
+            ),
+            TableError::NoHeaderRow => write!(f, "the table has no header row"),
+            TableError::CsvError(ref reason) => write!(f, "could not read CSV: {}", reason),
+            TableError::Io(ref e) => write!(f, "could not write table: {}", e),
+            TableError::Fmt(ref e) => write!(f, "could not write table: {}", e),
+        }
+    }
+}
+
+impl error::Error for TableError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match *self {
+            TableError::Io(ref e) => Some(e),
+            TableError::Fmt(ref e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for TableError {
+    fn from(e: io::Error) -> Self {
+        TableError::Io(e)
+    }
+}
+
+impl From<fmt::Error> for TableError {
+    fn from(e: fmt::Error) -> Self {
+        TableError::Fmt(e)
+    }
+}
+
+/// An error encountered while parsing a rendered table with `Table::parse`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    line: usize,
+    reason: &'static str,
+}
+
+impl ParseError {
+    pub(crate) fn new(line: usize, reason: &'static str) -> Self {
+        ParseError { line, reason }
+    }
+
+    /// The line that could not be parsed, counting from 1.
+    pub fn line(&self) -> usize {
+        self.line
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "could not parse table: line {} {}",
+            self.line, self.reason
+        )
+    }
+}
+
+impl error::Error for ParseError {}
@@ -0,0 +1,153 @@
+//! Rendering tables as HTML.
+
+use std::{fmt, fmt::Display, io};
+
+use {check_row_lengths, format_rows, write_io, LineEnding, TableError};
+
+/// Render the table as an HTML `<table>`
+///
+/// When `header` is true the first row is put in a `<thead>` using `<th>` cells. All other rows
+/// go in a `<tbody>` using `<td>` cells. Cell content is escaped, and no styling is added, so the
+/// snippet can be embedded anywhere.
+///
+/// ```
+/// let data = [["Name", "Age"], ["Alice", "42"]];
+/// let mut out = Vec::new();
+/// text_tables::render_html(&mut out, data, true).unwrap();
+/// assert_eq!(::std::str::from_utf8(&out).unwrap(), "\
+/// <table>
+/// <thead>
+/// <tr><th>Name</th><th>Age</th></tr>
+/// </thead>
+/// <tbody>
+/// <tr><td>Alice</td><td>42</td></tr>
+/// </tbody>
+/// </table>
+/// ");
+/// ```
+///
+/// # Errors
+///
+/// Fails if all rows are not the same length, or if writing fails.
+pub fn render_html<W, T, R, C>(writer: &mut W, data: T, header: bool) -> Result<(), TableError>
+where
+    W: io::Write,
+    T: AsRef<[R]>,
+    R: AsRef<[C]>,
+    C: Display,
+{
+    let rows = format_rows(data);
+    check_row_lengths(&rows)?;
+    write_io(writer, |writer| {
+        write_html(writer, &rows, header).map_err(From::from)
+    })
+}
+
+fn write_html<W: fmt::Write>(writer: &mut W, rows: &[Vec<String>], header: bool) -> fmt::Result {
+    let (head, body) = match rows.split_first() {
+        Some((first, rest)) if header => (Some(first), rest),
+        _ => (None, rows),
+    };
+    write!(writer, "<table>{}", LineEnding::Lf)?;
+    if let Some(head) = head {
+        write!(writer, "<thead>{}", LineEnding::Lf)?;
+        write_html_row(writer, head, "th")?;
+        write!(writer, "</thead>{}", LineEnding::Lf)?;
+    }
+    if !body.is_empty() {
+        write!(writer, "<tbody>{}", LineEnding::Lf)?;
+        for row in body {
+            write_html_row(writer, row, "td")?;
+        }
+        write!(writer, "</tbody>{}", LineEnding::Lf)?;
+    }
+    write!(writer, "</table>{}", LineEnding::Lf)
+}
+
+fn write_html_row<W: fmt::Write>(writer: &mut W, row: &[String], tag: &str) -> fmt::Result {
+    writer.write_str("<tr>")?;
+    for cell in row {
+        write!(writer, "<{}>", tag)?;
+        write_escaped(writer, cell)?;
+        write!(writer, "</{}>", tag)?;
+    }
+    write!(writer, "</tr>{}", LineEnding::Lf)
+}
+
+/// Write text with the characters that are special in HTML replaced by entities.
+fn write_escaped<W: fmt::Write>(writer: &mut W, text: &str) -> fmt::Result {
+    for c in text.chars() {
+        match c {
+            '<' => writer.write_str("&lt;")?,
+            '>' => writer.write_str("&gt;")?,
+            '&' => writer.write_str("&amp;")?,
+            '"' => writer.write_str("&quot;")?,
+            c => writer.write_char(c)?,
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::render_html;
+
+    fn html(data: &[Vec<&str>], header: bool) -> String {
+        let mut out = Vec::new();
+        render_html(&mut out, data, header).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn render() {
+        assert_eq!(html(&[], true), "<table>\n</table>\n");
+        assert_eq!(
+            html(&[vec!["a", "b"], vec!["c", "d"]], false),
+            "\
+<table>
+<tbody>
+<tr><td>a</td><td>b</td></tr>
+<tr><td>c</td><td>d</td></tr>
+</tbody>
+</table>
+"
+        );
+        assert_eq!(
+            html(&[vec!["a", "b"]], true),
+            "\
+<table>
+<thead>
+<tr><th>a</th><th>b</th></tr>
+</thead>
+</table>
+"
+        );
+    }
+
+    #[test]
+    fn escaping() {
+        assert_eq!(
+            html(
+                &[vec!["<script>alert(\"hi\")</script>"], vec!["fish & chips"]],
+                true
+            ),
+            "\
+<table>
+<thead>
+<tr><th>&lt;script&gt;alert(&quot;hi&quot;)&lt;/script&gt;</th></tr>
+</thead>
+<tbody>
+<tr><td>fish &amp; chips</td></tr>
+</tbody>
+</table>
+"
+        );
+    }
+
+    #[test]
+    fn unequal_row_lengths() {
+        let mut out = Vec::new();
+        assert!(render_html(&mut out, &[vec!["a", "b"], vec!["c"]], false).is_err());
+        assert!(out.is_empty());
+    }
+}
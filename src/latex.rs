@@ -0,0 +1,139 @@
+//! Rendering tables as LaTeX.
+
+use std::{fmt, fmt::Display, io};
+
+use {check_row_lengths, format_rows, write_io, Alignment, LineEnding, TableError};
+
+/// Render the table as a LaTeX `tabular` environment
+///
+/// Each column is aligned according to `alignments` (`l`, `r` or `c`), with columns that have
+/// no entry aligned left. Every row is separated by an `\hline`, like the borders in `render`.
+/// Characters that are special in LaTeX are escaped. An empty table renders nothing.
+///
+/// ```
+/// use text_tables::Alignment;
+///
+/// let data = [["Item", "Cost"], ["Tea", "$1.50"]];
+/// let mut out = Vec::new();
+/// text_tables::render_latex(&mut out, data, &[Alignment::Left, Alignment::Right]).unwrap();
+/// assert_eq!(::std::str::from_utf8(&out).unwrap(), r"\begin{tabular}{|l|r|}
+/// \hline
+/// Item & Cost \\
+/// \hline
+/// Tea & \$1.50 \\
+/// \hline
+/// \end{tabular}
+/// ");
+/// ```
+///
+/// # Errors
+///
+/// Fails if all rows are not the same length, or if writing fails.
+pub fn render_latex<W, T, R, C>(
+    writer: &mut W,
+    data: T,
+    alignments: &[Alignment],
+) -> Result<(), TableError>
+where
+    W: io::Write,
+    T: AsRef<[R]>,
+    R: AsRef<[C]>,
+    C: Display,
+{
+    let rows = format_rows(data);
+    check_row_lengths(&rows)?;
+    write_io(writer, |writer| {
+        write_latex(writer, &rows, alignments).map_err(From::from)
+    })
+}
+
+fn write_latex<W: fmt::Write>(
+    writer: &mut W,
+    rows: &[Vec<String>],
+    alignments: &[Alignment],
+) -> fmt::Result {
+    let columns = match rows.first() {
+        Some(row) if !row.is_empty() => row.len(),
+        _ => return Ok(()),
+    };
+    writer.write_str("\\begin{tabular}{|")?;
+    for idx in 0..columns {
+        let spec = match alignments.get(idx).cloned().unwrap_or_default() {
+            Alignment::Left => 'l',
+            Alignment::Right => 'r',
+            Alignment::Center => 'c',
+        };
+        write!(writer, "{}|", spec)?;
+    }
+    write!(writer, "}}{}", LineEnding::Lf)?;
+    write!(writer, "\\hline{}", LineEnding::Lf)?;
+    for row in rows {
+        for (idx, cell) in row.iter().enumerate() {
+            if idx > 0 {
+                writer.write_str(" & ")?;
+            }
+            write_escaped(writer, cell)?;
+        }
+        write!(writer, " \\\\{}", LineEnding::Lf)?;
+        write!(writer, "\\hline{}", LineEnding::Lf)?;
+    }
+    write!(writer, "\\end{{tabular}}{}", LineEnding::Lf)
+}
+
+/// Write text with the characters that are special in LaTeX escaped.
+fn write_escaped<W: fmt::Write>(writer: &mut W, text: &str) -> fmt::Result {
+    for c in text.chars() {
+        match c {
+            '&' | '%' | '$' | '#' | '_' | '{' | '}' => write!(writer, "\\{}", c)?,
+            '~' => writer.write_str("\\textasciitilde{}")?,
+            '^' => writer.write_str("\\textasciicircum{}")?,
+            '\\' => writer.write_str("\\textbackslash{}")?,
+            c => writer.write_char(c)?,
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::render_latex;
+    use Alignment;
+
+    fn latex(data: &[Vec<&str>], alignments: &[Alignment]) -> String {
+        let mut out = Vec::new();
+        render_latex(&mut out, data, alignments).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn render() {
+        assert_eq!(latex(&[], &[]), "");
+        assert_eq!(
+            latex(
+                &[vec!["a", "b", "c"], vec!["1", "2", "3"]],
+                &[Alignment::Center, Alignment::Right]
+            ),
+            r"\begin{tabular}{|c|r|l|}
+\hline
+a & b & c \\
+\hline
+1 & 2 & 3 \\
+\hline
+\end{tabular}
+"
+        );
+    }
+
+    #[test]
+    fn escaping() {
+        assert_eq!(
+            latex(&[vec!["50% of $5 & #1_{x}", r"~^\"]], &[]),
+            r"\begin{tabular}{|l|l|}
+\hline
+50\% of \$5 \& \#1\_\{x\} & \textasciitilde{}\textasciicircum{}\textbackslash{} \\
+\hline
+\end{tabular}
+"
+        );
+    }
+}
@@ -11,143 +11,3033 @@
 
 use std::{
     cmp,
-    fmt::{Display, Write},
-    io,
+    fmt::{self, Display},
+    io, iter,
 };
 
-const CORNER_STR: &'static str = "+";
-const HORIZ_BORDER_STR: &'static str = "-";
-const VERT_BORDER_STR: &'static str = "|";
-const SPACE_STR: &'static str = " ";
-const NEW_LINE_STR: &'static str = "\n";
+mod cell;
+mod chart;
+mod column;
+#[cfg(feature = "csv")]
+mod csv;
+mod display;
+mod error;
+mod html;
+mod latex;
+pub mod low_level;
+mod markdown;
+mod options;
+mod org;
+mod parse;
+mod style;
+mod summary;
+mod table;
+mod width;
+mod wrap;
+mod writer;
 
-/// Render the table to a writer
+pub use cell::{render_option, Cell, HyperLink, OptCell};
+pub use chart::{BarCell, PercentCell, Sparkline};
+pub use column::{CellFormatter, ColumnDef};
+#[cfg(feature = "csv")]
+pub use csv::{render_csv, render_delimited};
+pub use display::{table_display, TableDisplay};
+pub use error::{ParseError, TableError};
+pub use html::render_html;
+pub use latex::render_latex;
+pub use markdown::render_markdown;
+pub use options::{
+    Alignment, BorderMode, ColumnWidthPolicy, LineEnding, Options, Overflow, RowStyle, RowStyleFn,
+};
+pub use org::render_org;
+pub use style::{BoxStyle, Style};
+pub use summary::{render_summary, render_with_column_totals, ColumnStat};
+pub use table::Table;
+pub use writer::{TableWriter, WidthAccumulator};
+
+use style::BorderLine;
+use width::str_width;
+
+const SPACE_STR: &str = " ";
+/// Turns off any ANSI colours and text styles.
+const ANSI_RESET: &str = "\x1b[0m";
+
+/// Render the table to a writer, returning the number of bytes written
 ///
-/// Note that there are a lot of write calls, use a BufferedWriter if your writer is I/O for better
-/// performance.
+/// The table is built up in memory and written in one go, so there is no need to wrap `writer`
+/// in a `BufWriter`.
 ///
-/// # Panics
+/// ```
+/// let data = [["a", "b"]];
+/// let mut out = Vec::new();
+/// let written = text_tables::render(&mut out, data).unwrap();
+/// assert_eq!(written, out.len());
+/// ```
+///
+/// # Errors
 ///
-/// Will panic if all rows are not the same length
-pub fn render<W, T, R, C>(writer: &mut W, data: T) -> io::Result<()>
+/// Fails if all rows are not the same length, or if writing fails.
+pub fn render<W, T, R, C>(writer: &mut W, data: T) -> Result<usize, TableError>
 where
     W: io::Write,
     T: AsRef<[R]>,
     R: AsRef<[C]>,
     C: Display,
 {
-    let widths = widths(&data);
-    let data = data.as_ref();
+    let mut writer = CountingWriter::new(writer);
+    render_with_options(&mut writer, data, &Options::default())?;
+    Ok(writer.count)
+}
+
+/// Passes writes through to another writer, counting the bytes written.
+struct CountingWriter<'a, W: 'a> {
+    inner: &'a mut W,
+    count: usize,
+}
 
-    render_border_line(writer, &widths)?;
-    for row in data.iter() {
-        let row = row.as_ref();
-        render_text_line(writer, &widths, row)?;
-        render_border_line(writer, &widths)?;
+impl<'a, W: io::Write> CountingWriter<'a, W> {
+    fn new(inner: &'a mut W) -> Self {
+        CountingWriter { inner, count: 0 }
     }
+}
 
-    Ok(())
+impl<'a, W: io::Write> io::Write for CountingWriter<'a, W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let written = self.inner.write(buf)?;
+        self.count += written;
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
 }
 
-// Internal helpers
-// ================
+/// Render the table to a writer, using the given border style
+///
+/// `render` is a shortcut for this function with `Style::ascii()`.
+///
+/// # Errors
+///
+/// Fails if all rows are not the same length, or if writing fails.
+pub fn render_with_style<W, T, R, C>(
+    writer: &mut W,
+    data: T,
+    style: &Style,
+) -> Result<(), TableError>
+where
+    W: io::Write,
+    T: AsRef<[R]>,
+    R: AsRef<[C]>,
+    C: Display,
+{
+    let options = Options {
+        style: *style,
+        ..Options::default()
+    };
+    render_with_options(writer, data, &options)
+}
 
-/// Get the largest width of each column.
-fn widths<T, R, C>(data: T) -> Vec<usize>
+/// Render the table to a writer, treating the first row as a header
+///
+/// The header is separated from the rest of the table by a line of `=`.
+///
+/// # Errors
+///
+/// Fails if all rows are not the same length, or if writing fails.
+pub fn render_with_header<W, T, R, C>(writer: &mut W, data: T) -> Result<(), TableError>
 where
+    W: io::Write,
     T: AsRef<[R]>,
     R: AsRef<[C]>,
     C: Display,
 {
-    // re-use a string to reduce memory allocs.
-    let mut string_buf = String::new();
-    let data = data.as_ref();
-    // bail early if there is nothing to do
-    if data.len() == 0 {
-        return vec![];
+    let options = Options {
+        header_row: true,
+        ..Options::default()
+    };
+    render_with_options(writer, data, &options)
+}
+
+/// Render the table to a writer, with a column on the left numbering each row
+///
+/// Rows are numbered counting up from `start`.
+///
+/// # Errors
+///
+/// Fails if all rows are not the same length, or if writing fails.
+pub fn render_with_row_numbers<W, T, R, C>(
+    writer: &mut W,
+    data: T,
+    start: usize,
+) -> Result<(), TableError>
+where
+    W: io::Write,
+    T: AsRef<[R]>,
+    R: AsRef<[C]>,
+    C: Display,
+{
+    table_from_data(data).number_rows(start).render(writer)
+}
+
+/// Render the table to a writer, with a blank row after each row index in `separator_after`
+///
+/// This is useful for separating groups of rows. Separators after the last row are ignored.
+///
+/// # Errors
+///
+/// Fails if all rows are not the same length, or if writing fails.
+pub fn render_with_separator_rows<W, T, R, C>(
+    writer: &mut W,
+    data: T,
+    separator_after: &[usize],
+) -> Result<(), TableError>
+where
+    W: io::Write,
+    T: AsRef<[R]>,
+    R: AsRef<[C]>,
+    C: Display,
+{
+    let rows = format_rows(data);
+    let last = rows.len().saturating_sub(1);
+    let mut table = Table::new();
+    for (idx, row) in rows.into_iter().enumerate() {
+        let columns = row.len();
+        table.add_row(row);
+        if idx != last {
+            for _ in separator_after.iter().filter(|&&after| after == idx) {
+                table.add_row(vec![""; columns]);
+            }
+        }
     }
-    // this would panic without len check above
-    let row_len = data[0].as_ref().len();
-    let mut widths = vec![0; row_len];
-    for row in data.iter() {
-        let row = row.as_ref();
-        if row_len != row.len() {
-            // todo better handle this situation
-            panic!("rows must be the same length");
+    table.render(writer)
+}
+
+/// Render the table to a writer, with rows and columns swapped
+///
+/// # Errors
+///
+/// Fails if all rows are not the same length, or if writing fails.
+pub fn render_transposed<W, T, R, C>(writer: &mut W, data: T) -> Result<(), TableError>
+where
+    W: io::Write,
+    T: AsRef<[R]>,
+    R: AsRef<[C]>,
+    C: Display,
+{
+    render_with_options(writer, transpose(data)?, &Options::default())
+}
+
+/// Swap the rows and columns of the data, formatting each cell as a `String`
+///
+/// The cell in the first row and column stays where it is.
+///
+/// # Errors
+///
+/// Fails if all rows are not the same length.
+pub fn transpose<T, R, C>(data: T) -> Result<Vec<Vec<String>>, TableError>
+where
+    T: AsRef<[R]>,
+    R: AsRef<[C]>,
+    C: Display,
+{
+    let rows = format_rows(data);
+    check_row_lengths(&rows)?;
+    Ok(table::transpose_rows(rows))
+}
+
+/// Render the table as a reStructuredText grid table
+///
+/// The first row is used as the header. Grid tables use the same borders as `render`, with a line
+/// of `=` below the header.
+///
+/// # Errors
+///
+/// Fails if all rows are not the same length, or if writing fails.
+pub fn render_rst<W, T, R, C>(writer: &mut W, data: T) -> Result<(), TableError>
+where
+    W: io::Write,
+    T: AsRef<[R]>,
+    R: AsRef<[C]>,
+    C: Display,
+{
+    let options = Options {
+        style: Style {
+            header_horizontal: '=',
+            ..Style::ascii()
+        },
+        header_row: true,
+        ..Options::default()
+    };
+    render_with_options(writer, data, &options)
+}
+
+/// Check whether two tables have the same cells
+///
+/// Cells are compared by their `Display` output, so tables built from different types can be
+/// equal, and it doesn't matter what style they would be rendered with.
+pub fn tables_equal<T1, T2, R, C>(a: T1, b: T2) -> bool
+where
+    T1: AsRef<[R]>,
+    T2: AsRef<[R]>,
+    R: AsRef<[C]>,
+    C: Display,
+{
+    format_rows(a) == format_rows(b)
+}
+
+/// Render the table to a writer, leaving out columns on the right so it is no wider than
+/// `max_width`
+///
+/// Columns are kept from the left for as long as they fit. If any are left out, a column of `…`
+/// is added on the right to show where. This column is always shown when columns are left out,
+/// even if `max_width` is too small to fit it.
+///
+/// # Errors
+///
+/// Fails if all rows are not the same length, or if writing fails.
+pub fn render_truncated<W, T, R, C>(
+    writer: &mut W,
+    data: T,
+    max_width: usize,
+) -> Result<(), TableError>
+where
+    W: io::Write,
+    T: AsRef<[R]>,
+    R: AsRef<[C]>,
+    C: Display,
+{
+    const MARKER: &str = "…";
+
+    let options = Options::default();
+    let mut rows = format_rows(data);
+    let widths = widths(&rows, &options)?;
+    if compute_total_width(&widths, options.padding) > max_width {
+        let marker_width = str_width(MARKER);
+        let mut kept = widths.len();
+        while kept > 0 {
+            kept -= 1;
+            let mut kept_widths = widths[..kept].to_vec();
+            kept_widths.push(marker_width);
+            if compute_total_width(&kept_widths, options.padding) <= max_width {
+                break;
+            }
         }
-        for (idx, cell) in row.iter().enumerate() {
-            string_buf.clear();
-            write!(string_buf, "{}", cell).unwrap(); // writing to a string cannot fail.
-            widths[idx] = cmp::max(widths[idx], string_buf.len());
+        for row in &mut rows {
+            row.truncate(kept);
+            row.push(MARKER.to_string());
         }
     }
-    widths
+    rows.into_iter()
+        .collect::<Table>()
+        .render_with_options(writer, &options)
 }
 
-/// Render a border line
-fn render_border_line<W: io::Write>(writer: &mut W, lengths: &[usize]) -> io::Result<()> {
-    if lengths.len() == 0 || lengths[0] == 0 {
-        return Ok(());
+/// Render the table to a writer, shrinking the columns so it is no wider than `max_width`
+///
+/// If the table is too wide, every column is narrowed by the same proportion, though never below
+/// one character. Cells that no longer fit are truncated with `…`. If `max_width` is too small
+/// for every column to be one character wide, the table will be wider than `max_width`.
+///
+/// # Errors
+///
+/// Fails if all rows are not the same length, or if writing fails.
+pub fn render_with_proportional_shrink<W, T, R, C>(
+    writer: &mut W,
+    data: T,
+    max_width: usize,
+) -> Result<(), TableError>
+where
+    W: io::Write,
+    T: AsRef<[R]>,
+    R: AsRef<[C]>,
+    C: Display,
+{
+    let rows = format_rows(data);
+    let mut options = Options::default();
+    let widths = widths(&rows, &options)?;
+    let borders = compute_total_width(&widths, options.padding) - widths.iter().sum::<usize>();
+    let shrunk = shrink_widths(&widths, max_width.saturating_sub(borders));
+    if shrunk != widths {
+        options.max_column_widths = shrunk.into_iter().map(Some).collect();
+        options.overflow = Overflow::Truncate;
+    }
+    rows.into_iter()
+        .collect::<Table>()
+        .render_with_options(writer, &options)
+}
+
+/// Scale `widths` down to fit in `budget`, keeping their proportions where possible.
+///
+/// Each column is at least 1 wide, unless it was narrower to start with.
+fn shrink_widths(widths: &[usize], budget: usize) -> Vec<usize> {
+    if widths.iter().sum::<usize>() <= budget {
+        return widths.to_vec();
     }
-    write!(writer, "{}", CORNER_STR)?;
-    for len in lengths {
-        for _ in 0..(*len + 2) {
-            write!(writer, "{}", HORIZ_BORDER_STR)?;
+    // columns that would shrink below 1 are pinned, and the rest share what's left.
+    let mut pinned = widths.iter().map(|&width| width == 0).collect::<Vec<_>>();
+    let mut shrunk = widths.to_vec();
+    loop {
+        let pinned_total = (0..widths.len())
+            .filter(|&idx| pinned[idx])
+            .map(|idx| cmp::min(widths[idx], 1))
+            .sum::<usize>();
+        let free_total = (0..widths.len())
+            .filter(|&idx| !pinned[idx])
+            .map(|idx| widths[idx])
+            .sum::<usize>();
+        let free_budget = budget.saturating_sub(pinned_total);
+        let mut changed = false;
+        for idx in 0..widths.len() {
+            if pinned[idx] {
+                shrunk[idx] = cmp::min(widths[idx], 1);
+                continue;
+            }
+            shrunk[idx] = widths[idx] * free_budget / free_total;
+            if shrunk[idx] == 0 {
+                pinned[idx] = true;
+                changed = true;
+            }
+        }
+        if !changed {
+            break;
+        }
+    }
+    // hand out any space lost to rounding down, from the left.
+    let mut spare = budget.saturating_sub(shrunk.iter().sum());
+    for (width, natural) in shrunk.iter_mut().zip(widths) {
+        if spare == 0 {
+            break;
+        }
+        if *width < *natural {
+            *width += 1;
+            spare -= 1;
         }
-        write!(writer, "{}", CORNER_STR)?;
     }
-    write!(writer, "\n")
+    shrunk
 }
 
-/// Render a text line
-fn render_text_line<W, C>(writer: &mut W, lengths: &[usize], row: &[C]) -> io::Result<()>
+/// Render a single table showing the differences between `before` and `after`
+///
+/// Cells that are the same in both are shown as they are. Cells that changed are shown as
+/// `before → after`, starting with `changed_marker`. Cells missing from one of the tables count
+/// as empty.
+///
+/// If `changed_marker` is an ANSI escape code, such as `"\x1b[33m"`, changed cells are
+/// highlighted in that colour, and the code is not counted towards the column widths.
+///
+/// # Errors
+///
+/// Fails if all rows of the combined table are not the same length, or if writing fails.
+pub fn render_diff<W, T, R, C>(
+    writer: &mut W,
+    before: T,
+    after: T,
+    changed_marker: &str,
+) -> Result<(), TableError>
 where
     W: io::Write,
+    T: AsRef<[R]>,
+    R: AsRef<[C]>,
     C: Display,
 {
-    if lengths.len() == 0 || lengths[0] == 0 {
-        return Ok(());
-    }
-    let mut string_buf = String::new();
-    write!(writer, "{}", VERT_BORDER_STR)?;
-    for (cell, len) in row.iter().zip(lengths.iter()) {
-        string_buf.clear();
-        write!(string_buf, "{}", cell).unwrap(); // writing to string cannot fail.
-        let extra = len - string_buf.len();
-        write!(writer, "{}{}", SPACE_STR, string_buf)?;
-        for _ in 0..extra + 1 {
-            write!(writer, "{}", SPACE_STR)?;
+    let before = format_rows(before);
+    let after = format_rows(after);
+    let highlight = changed_marker.starts_with('\x1b');
+    let get = |rows: &[Vec<String>], row: usize, col: usize| {
+        rows.get(row)
+            .and_then(|row| row.get(col))
+            .cloned()
+            .unwrap_or_default()
+    };
+    let row_count = cmp::max(before.len(), after.len());
+    let diff = (0..row_count)
+        .map(|row| {
+            let columns = cmp::max(
+                before.get(row).map_or(0, Vec::len),
+                after.get(row).map_or(0, Vec::len),
+            );
+            (0..columns)
+                .map(|col| {
+                    let (old, new) = (get(&before, row, col), get(&after, row, col));
+                    if old == new {
+                        old
+                    } else if highlight {
+                        format!("{}{} → {}{}", changed_marker, old, new, ANSI_RESET)
+                    } else {
+                        format!("{}{} → {}", changed_marker, old, new)
+                    }
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect::<Table>();
+    let options = Options {
+        strip_ansi_for_width: highlight,
+        ..Options::default()
+    };
+    diff.render_with_options(writer, &options)
+}
+
+/// Render two tables next to each other, with `gap` spaces between them
+///
+/// Useful for comparing two versions of a table. If one table is shorter than the other, it is
+/// padded with blank lines at the bottom.
+///
+/// # Errors
+///
+/// Fails if all rows of either table are not the same length, or if writing fails.
+pub fn render_side_by_side<W, T, R, C>(
+    writer: &mut W,
+    left: T,
+    right: T,
+    gap: usize,
+) -> Result<(), TableError>
+where
+    W: io::Write,
+    T: AsRef<[R]>,
+    R: AsRef<[C]>,
+    C: Display,
+{
+    let mut left_text = String::new();
+    table_from_data(left).render_fmt(&mut left_text)?;
+    let mut right_text = String::new();
+    table_from_data(right).render_fmt(&mut right_text)?;
+    write_io(writer, |writer| {
+        write_side_by_side(writer, &left_text, &right_text, gap)?;
+        Ok(())
+    })
+}
+
+/// Write two rendered tables next to each other.
+fn write_side_by_side<W: fmt::Write>(
+    writer: &mut W,
+    left: &str,
+    right: &str,
+    gap: usize,
+) -> fmt::Result {
+    let left_lines = left.lines().collect::<Vec<_>>();
+    let right_lines = right.lines().collect::<Vec<_>>();
+    let left_width = left_lines
+        .iter()
+        .map(|line| str_width(line))
+        .max()
+        .unwrap_or(0);
+    for idx in 0..cmp::max(left_lines.len(), right_lines.len()) {
+        let left_line = left_lines.get(idx).cloned().unwrap_or("");
+        write!(writer, "{}", left_line)?;
+        if let Some(right_line) = right_lines.get(idx) {
+            write_spaces(writer, left_width - str_width(left_line) + gap)?;
+            write!(writer, "{}", right_line)?;
         }
-        write!(writer, "{}", VERT_BORDER_STR)?;
+        write!(writer, "{}", LineEnding::default())?;
     }
-    write!(writer, "{}", NEW_LINE_STR)?;
+    Ok(())
+}
+
+/// Render the table centered within a space `total_width` characters wide
+///
+/// Each line is indented by half the space left over, rounded down. If the table is wider than
+/// `total_width`, it is rendered as normal.
+///
+/// ```
+/// let mut out = Vec::new();
+/// text_tables::render_centered_in_width(&mut out, [["a"]], 9).unwrap();
+/// assert_eq!(
+///     ::std::str::from_utf8(&out).unwrap(),
+///     "  +---+\n  | a |\n  +---+\n"
+/// );
+/// ```
+///
+/// # Errors
+///
+/// Fails if all rows are not the same length, or if writing fails.
+pub fn render_centered_in_width<W, T, R, C>(
+    writer: &mut W,
+    data: T,
+    total_width: usize,
+) -> Result<(), TableError>
+where
+    W: io::Write,
+    T: AsRef<[R]>,
+    R: AsRef<[C]>,
+    C: Display,
+{
+    let table = table_from_data(data);
+    let options = Options::default();
+    let (width, _) = table.dimensions(&options)?;
+    let indent = total_width.saturating_sub(width) / 2;
+    let mut text = String::new();
+    table.write_table(&mut text, &options)?;
+    write_io(writer, |writer| {
+        write_indented(writer, &text, indent)?;
+        Ok(())
+    })
+}
 
+/// Write each line of a rendered table after `indent` spaces.
+fn write_indented<W: fmt::Write>(writer: &mut W, text: &str, indent: usize) -> fmt::Result {
+    for line in text.lines() {
+        write_spaces(writer, indent)?;
+        write!(writer, "{}{}", line, LineEnding::default())?;
+    }
     Ok(())
 }
 
-#[cfg(test)]
-mod tests {
+/// Render the table in the style of the MySQL command line client
+///
+/// The first row is used as the header. Unlike `render_with_header`, the header is separated
+/// with `-`, and there are no borders between the other rows. MySQL right-aligns numeric
+/// columns, which can be done with `render_with_options` and `Options::alignments`.
+///
+/// # Errors
+///
+/// Fails if all rows are not the same length, or if writing fails.
+pub fn render_mysql<W, T, R, C>(writer: &mut W, data: T) -> Result<(), TableError>
+where
+    W: io::Write,
+    T: AsRef<[R]>,
+    R: AsRef<[C]>,
+    C: Display,
+{
+    render_with_options(writer, data, &mysql_options())
+}
 
-    #[test]
-    fn render() {
-        let tables = vec![
-            (vec![], &b""[..]),
-            (vec![vec![]], &b""[..]),
-            (
-                vec![vec!["single", "line", "a"], vec!["second", "lines", "a"]],
-                &b"\
-+--------+-------+---+
-| single | line  | a |
-+--------+-------+---+
-| second | lines | a |
-+--------+-------+---+
-"[..],
-            ),
-        ];
-        for (table, result) in tables {
-            let mut out = Vec::new();
-            super::render(&mut out, &table).unwrap();
-            assert_eq!(out, &result[..], "{:#?}", table);
+/// The options that match the output of the MySQL command line client.
+fn mysql_options() -> Options {
+    Options {
+        style: Style::mysql(),
+        header_row: true,
+        border_mode: BorderMode::Compact,
+        ..Options::default()
+    }
+}
+
+/// Render the table in the style of PostgreSQL's `psql`
+///
+/// The first row is used as the header, and there are no borders around the edges of the table
+/// or between the other rows.
+///
+/// ```
+/// let data = [["id", "name"], ["1", "alice"], ["2", "bob"]];
+/// let mut out = Vec::new();
+/// text_tables::render_psql(&mut out, data).unwrap();
+/// assert_eq!(::std::str::from_utf8(&out).unwrap(), concat!(
+///     " id | name\n",
+///     "----+-------\n",
+///     " 1  | alice\n",
+///     " 2  | bob\n",
+/// ));
+/// ```
+///
+/// # Errors
+///
+/// Fails if all rows are not the same length, or if writing fails.
+pub fn render_psql<W, T, R, C>(writer: &mut W, data: T) -> Result<(), TableError>
+where
+    W: io::Write,
+    T: AsRef<[R]>,
+    R: AsRef<[C]>,
+    C: Display,
+{
+    let options = Options {
+        style: Style::psql(),
+        header_row: true,
+        border_mode: BorderMode::Compact,
+        ..Options::default()
+    };
+    render_with_options(writer, data, &options)
+}
+
+/// Render the table, followed by a list of footnotes
+///
+/// Each footnote is written on its own line below the table as `[N] text`, so cells can refer
+/// to it by ending in `[N]`. The table itself is rendered as normal.
+///
+/// ```
+/// let data = [["Item", "Cost"], ["Tea", "1.50[1]"]];
+/// let mut out = Vec::new();
+/// text_tables::render_with_footnotes(&mut out, data, &[(1, "Includes milk.")]).unwrap();
+/// assert_eq!(::std::str::from_utf8(&out).unwrap(), "\
+/// +------+---------+
+/// | Item | Cost    |
+/// +------+---------+
+/// | Tea  | 1.50[1] |
+/// +------+---------+
+/// [1] Includes milk.
+/// ");
+/// ```
+///
+/// # Errors
+///
+/// Fails if all rows are not the same length, or if writing fails.
+pub fn render_with_footnotes<W, T, R, C>(
+    writer: &mut W,
+    data: T,
+    footnotes: &[(usize, &str)],
+) -> Result<(), TableError>
+where
+    W: io::Write,
+    T: AsRef<[R]>,
+    R: AsRef<[C]>,
+    C: Display,
+{
+    let options = Options::default();
+    let table = table_from_data(data);
+    write_io(writer, |writer| {
+        table.write_table(writer, &options)?;
+        write_footnotes(writer, footnotes, &options)?;
+        Ok(())
+    })
+}
+
+/// Write each footnote on its own line, like `[1] text`.
+fn write_footnotes<W: fmt::Write>(
+    writer: &mut W,
+    footnotes: &[(usize, &str)],
+    options: &Options,
+) -> fmt::Result {
+    for &(number, text) in footnotes {
+        write!(writer, "[{}] {}{}", number, text, options.line_ending)?;
+    }
+    Ok(())
+}
+
+/// Render several groups of rows as tables, each below a label saying what the group is
+///
+/// Each label is centered above its group, without any borders, and a group without a label has
+/// a blank line instead. All the groups have the same column widths, so their columns line up.
+///
+/// ```
+/// let fruit = vec![vec!["apple", "3"]];
+/// let veg = vec![vec!["leek", "12"]];
+/// let mut out = Vec::new();
+/// text_tables::render_with_row_groups(&mut out, &[(Some("Fruit"), fruit), (None, veg)]).unwrap();
+/// assert_eq!(::std::str::from_utf8(&out).unwrap(), concat!(
+///     "    Fruit\n",
+///     "+-------+----+\n",
+///     "| apple | 3  |\n",
+///     "+-------+----+\n",
+///     "\n",
+///     "+-------+----+\n",
+///     "| leek  | 12 |\n",
+///     "+-------+----+\n",
+/// ));
+/// ```
+///
+/// # Errors
+///
+/// Fails if all rows, across all the groups, are not the same length, or if writing fails.
+pub fn render_with_row_groups<W, T, R, C>(
+    writer: &mut W,
+    groups: &[(Option<&str>, T)],
+) -> Result<(), TableError>
+where
+    W: io::Write,
+    T: AsRef<[R]>,
+    R: AsRef<[C]>,
+    C: Display,
+{
+    let groups = groups
+        .iter()
+        .map(|&(label, ref data)| (label, format_rows(data)))
+        .collect::<Vec<_>>();
+    let options = Options {
+        min_column_widths: widths(
+            groups.iter().flat_map(|group| &group.1),
+            &Options::default(),
+        )?,
+        ..Options::default()
+    };
+    write_io(writer, |writer| {
+        for &(label, ref rows) in &groups {
+            match label {
+                Some(label) => {
+                    render_title_line(writer, &options.min_column_widths, &options, label)?
+                }
+                None => writer.push_str(options.line_ending.as_str()),
+            }
+            rows.iter()
+                .collect::<Table>()
+                .write_table(writer, &options)?;
         }
+        Ok(())
+    })
+}
+
+/// Render pairs of keys and values as a two-column table, with a `Key` and `Value` header
+///
+/// ```
+/// let mut out = Vec::new();
+/// text_tables::render_kv(&mut out, &[("name", "Alice"), ("age", "42")]).unwrap();
+/// assert_eq!(::std::str::from_utf8(&out).unwrap(), "\
+/// +------+-------+
+/// | Key  | Value |
+/// +======+=======+
+/// | name | Alice |
+/// +------+-------+
+/// | age  | 42    |
+/// +------+-------+
+/// ");
+/// ```
+///
+/// # Errors
+///
+/// Fails if writing fails.
+pub fn render_kv<W, K, V>(writer: &mut W, pairs: &[(K, V)]) -> Result<(), TableError>
+where
+    W: io::Write,
+    K: Display,
+    V: Display,
+{
+    let mut table = Table::new();
+    table.add_header(["Key", "Value"]);
+    for (key, value) in pairs {
+        table.add_row([key.to_string(), value.to_string()]);
+    }
+    table.render(writer)
+}
+
+/// Render the table in pages, repeating the header at the top of each one
+///
+/// The first row is used as the header. After every `page_size` rows the table is closed off, and
+/// the header is drawn again above the next page, so it stays in view when the output is read
+/// through a pager. Every page has the same column widths. A `page_size` of 0 puts all the rows
+/// on one page.
+///
+/// ```
+/// let data = [["n"], ["1"], ["2"], ["3"]];
+/// let mut out = Vec::new();
+/// text_tables::render_paged(&mut out, data, 2).unwrap();
+/// assert_eq!(::std::str::from_utf8(&out).unwrap(), "\
+/// +---+
+/// | n |
+/// +===+
+/// | 1 |
+/// +---+
+/// | 2 |
+/// +---+
+/// +---+
+/// | n |
+/// +===+
+/// | 3 |
+/// +---+
+/// ");
+/// ```
+///
+/// # Errors
+///
+/// Fails if all rows are not the same length, or if writing fails.
+pub fn render_paged<W, T, R, C>(writer: &mut W, data: T, page_size: usize) -> Result<(), TableError>
+where
+    W: io::Write,
+    T: AsRef<[R]>,
+    R: AsRef<[C]>,
+    C: Display,
+{
+    render_with_page_breaks(writer, data, page_size, "")
+}
+
+/// Render the table in pages of `page_height` rows, with `page_break` between the pages
+///
+/// This is `render_paged` for output that is printed or split into pages, where `page_break` is
+/// something like a form feed (`"\x0c"`). It is written after the line that closes off each page
+/// but the last.
+///
+/// ```
+/// let data = [["n"], ["1"], ["2"]];
+/// let mut out = Vec::new();
+/// text_tables::render_with_page_breaks(&mut out, data, 1, "\x0c").unwrap();
+/// assert_eq!(
+///     ::std::str::from_utf8(&out).unwrap(),
+///     "+---+\n| n |\n+===+\n| 1 |\n+---+\n\x0c+---+\n| n |\n+===+\n| 2 |\n+---+\n"
+/// );
+/// ```
+///
+/// # Errors
+///
+/// Fails if all rows are not the same length, or if writing fails.
+pub fn render_with_page_breaks<W, T, R, C>(
+    writer: &mut W,
+    data: T,
+    page_height: usize,
+    page_break: &str,
+) -> Result<(), TableError>
+where
+    W: io::Write,
+    T: AsRef<[R]>,
+    R: AsRef<[C]>,
+    C: Display,
+{
+    let rows = format_rows(data);
+    let (header, body) = match rows.split_first() {
+        Some(split) => split,
+        None => return Ok(()),
+    };
+    let options = Options {
+        header_row: true,
+        // make every page as wide as the widest cells in the whole table.
+        min_column_widths: widths(&rows, &Options::default())?,
+        ..Options::default()
+    };
+    let page_height = if page_height == 0 {
+        body.len()
+    } else {
+        page_height
+    };
+    write_io(writer, |writer| {
+        if body.is_empty() {
+            return iter::once(header)
+                .collect::<Table>()
+                .write_table(writer, &options);
+        }
+        for (idx, page) in body.chunks(page_height).enumerate() {
+            if idx > 0 {
+                writer.push_str(page_break);
+            }
+            iter::once(header)
+                .chain(page)
+                .collect::<Table>()
+                .write_table(writer, &options)?;
+        }
+        Ok(())
+    })
+}
+
+/// Render the table to a writer, using the given options
+///
+/// All the other `render_*` functions are shortcuts for this one.
+///
+/// # Errors
+///
+/// Fails if all rows are not the same length, or if writing fails.
+pub fn render_with_options<W, T, R, C>(
+    writer: &mut W,
+    data: T,
+    options: &Options,
+) -> Result<(), TableError>
+where
+    W: io::Write,
+    T: AsRef<[R]>,
+    R: AsRef<[C]>,
+    C: Display,
+{
+    table_from_data(data).render_with_options(writer, options)
+}
+
+/// Render rows from an iterator to a writer
+///
+/// This behaves exactly like `render`, but the rows can be any iterator (for example a lazy
+/// `map` over some other collection). The rows are formatted once and collected up front, since
+/// the width of each column must be known before anything is written.
+///
+/// # Errors
+///
+/// Fails if all rows are not the same length, or if writing fails.
+pub fn render_iter<W, I, R, C>(writer: &mut W, rows: I) -> Result<(), TableError>
+where
+    W: io::Write,
+    I: IntoIterator<Item = R>,
+    R: IntoIterator<Item = C>,
+    C: Display,
+{
+    rows.into_iter().collect::<Table>().render(writer)
+}
+
+/// Render the table to a formatter
+///
+/// This is the same as `render`, but for `fmt::Write` targets, so it can be used from inside a
+/// `Display` impl.
+///
+/// # Errors
+///
+/// Fails if all rows are not the same length, or if writing fails.
+pub fn render_fmt<W, T, R, C>(writer: &mut W, data: T) -> Result<(), TableError>
+where
+    W: fmt::Write,
+    T: AsRef<[R]>,
+    R: AsRef<[C]>,
+    C: Display,
+{
+    table_from_data(data).render_fmt(writer)
+}
+
+/// Render the table to a `String`
+///
+/// This is a convenience wrapper around `render_fmt` for when you just want the table as text.
+///
+/// # Panics
+///
+/// Will panic if all rows are not the same length. Use `render_fmt` to handle this case.
+pub fn render_to_string<T, R, C>(data: T) -> String
+where
+    T: AsRef<[R]>,
+    R: AsRef<[C]>,
+    C: Display,
+{
+    let mut out = String::new();
+    // writing to a string cannot fail, so this can only be a problem with the data.
+    if let Err(e) = render_fmt(&mut out, data) {
+        panic!("{}", e);
+    }
+    out
+}
+
+/// Render the table to a `Vec<u8>`
+///
+/// This is a convenience wrapper around `render` for when you want the table as bytes, for
+/// example to pass to an API that takes `&[u8]`. The bytes are always valid UTF-8.
+///
+/// # Panics
+///
+/// Will panic if all rows are not the same length. Use `render` to handle this case.
+pub fn render_to_vec<T, R, C>(data: T) -> Vec<u8>
+where
+    T: AsRef<[R]>,
+    R: AsRef<[C]>,
+    C: Display,
+{
+    let table = table_from_data(data);
+    let mut out = Vec::new();
+    // writing to a vec cannot fail, so this can only be a problem with the data.
+    let written = write_io_with_capacity(&mut out, table.estimate_size(), |writer| {
+        table.render_fmt(writer)
+    });
+    if let Err(e) = written {
+        panic!("{}", e);
+    }
+    out
+}
+
+// Internal helpers
+// ================
+
+/// Copy the data into a `Table`.
+fn table_from_data<T, R, C>(data: T) -> Table
+where
+    T: AsRef<[R]>,
+    R: AsRef<[C]>,
+    C: Display,
+{
+    data.as_ref().iter().map(AsRef::as_ref).collect()
+}
+
+/// Format every cell of the data using its `Display` impl.
+fn format_rows<T, R, C>(data: T) -> Vec<Vec<String>>
+where
+    T: AsRef<[R]>,
+    R: AsRef<[C]>,
+    C: Display,
+{
+    data.as_ref()
+        .iter()
+        .map(|row| row.as_ref().iter().map(ToString::to_string).collect())
+        .collect()
+}
+
+/// Check that all rows are the same length as the first.
+fn check_row_lengths(rows: &[Vec<String>]) -> Result<(), TableError> {
+    let expected = match rows.first() {
+        Some(row) => row.len(),
+        None => return Ok(()),
+    };
+    match rows.iter().position(|row| row.len() != expected) {
+        Some(row_index) => Err(TableError::UnequalRowLengths {
+            expected,
+            got: rows[row_index].len(),
+            row_index,
+        }),
+        None => Ok(()),
+    }
+}
+
+/// Get the largest width of each column.
+///
+/// Fails if all rows are not the same length.
+fn widths<'a, I>(rows: I, options: &Options) -> Result<Vec<usize>, TableError>
+where
+    I: IntoIterator<Item = &'a Vec<String>>,
+{
+    let mut rows = rows.into_iter();
+    // bail early if there is nothing to do
+    let mut widths = match rows.next() {
+        Some(row) => row
+            .iter()
+            .map(|cell| cell_width(cell, options))
+            .collect::<Vec<_>>(),
+        None => return Ok(vec![]),
+    };
+    for (row_index, row) in rows.enumerate() {
+        if widths.len() != row.len() {
+            return Err(TableError::UnequalRowLengths {
+                expected: widths.len(),
+                got: row.len(),
+                row_index: row_index + 1,
+            });
+        }
+        for (width, cell) in widths.iter_mut().zip(row.iter()) {
+            *width = cmp::max(*width, cell_width(cell, options));
+        }
+    }
+    Ok(widths)
+}
+
+/// Get the width of a cell, which is the width of its longest line.
+fn cell_width(cell: &str, options: &Options) -> usize {
+    cell.lines()
+        .map(|line| options.text_width(line))
+        .max()
+        .unwrap_or(0)
+}
+
+/// Get the width of each column's content, in characters, without rendering the table
+///
+/// These are the widths `render` would use, not counting padding or borders. Characters are
+/// measured by their display width, so wide characters count for two when the `unicode` feature
+/// is on.
+///
+/// ```
+/// let data = [["name", "age"], ["Alice", "42"]];
+/// assert_eq!(text_tables::column_widths(&data), vec![5, 3]);
+/// ```
+///
+/// # Panics
+///
+/// Will panic if all rows are not the same length.
+pub fn column_widths<T, R, C>(data: &T) -> Vec<usize>
+where
+    T: AsRef<[R]>,
+    R: AsRef<[C]>,
+    C: Display,
+{
+    match widths(&format_rows(data), &Options::default()) {
+        Ok(widths) => widths,
+        Err(e) => panic!("{}", e),
+    }
+}
+
+/// Get the width and height of the table, in characters and lines, without rendering it
+///
+/// This takes everything in `options` into account, including borders, padding, wrapping and
+/// the title.
+///
+/// # Panics
+///
+/// Will panic if all rows are not the same length.
+pub fn compute_dimensions<T, R, C>(data: &T, options: &Options) -> (usize, usize)
+where
+    T: AsRef<[R]>,
+    R: AsRef<[C]>,
+    C: Display,
+{
+    match table_from_data(data).dimensions(options) {
+        Ok(dimensions) => dimensions,
+        Err(e) => panic!("{}", e),
+    }
+}
+
+/// Get the total width of a rendered table with borders, given the width of each column's content
+/// and the padding on each side of it.
+///
+/// ```
+/// // | a  | bc |
+/// assert_eq!(text_tables::compute_total_width(&[2, 2], 1), 11);
+/// ```
+pub fn compute_total_width(widths: &[usize], padding: usize) -> usize {
+    if widths.is_empty() {
+        return 0;
+    }
+    widths
+        .iter()
+        .map(|width| width + 2 * padding)
+        .sum::<usize>()
+        + widths.len()
+        + 1
+}
+
+/// Guess how many bytes a table rendered with the default style will take up, so its buffer can
+/// be allocated up front.
+///
+/// `row_count` is the number of lines of text. Every line is as wide as the table plus a line
+/// break, and there is a border line above, below and between each line of text. This is an upper
+/// bound as long as each of `widths` is at least the number of bytes a line of that column takes
+/// up once it has been padded out, which is more than its display width for non-ASCII text.
+fn estimate_buffer_size(widths: &[usize], row_count: usize, padding: usize) -> usize {
+    let line = widths.iter().sum::<usize>() + widths.len() * (2 * padding + 1) + 1;
+    (line + 1) * (2 * row_count + 1)
+}
+
+/// Run `f` to build up the output in a buffer, then write it to `writer` all at once.
+///
+/// All the rendering code is written against `fmt::Write`, and `io::Write` targets go through
+/// here, so the two kinds of writer can never produce different output. Buffering means the
+/// writer sees a single `write_all` instead of one write per border and cell.
+fn write_io<W, F>(writer: &mut W, f: F) -> Result<(), TableError>
+where
+    W: io::Write,
+    F: FnOnce(&mut String) -> Result<(), TableError>,
+{
+    write_io_with_capacity(writer, 0, f)
+}
+
+/// Like `write_io`, but starting with a buffer that has room for `capacity` bytes.
+fn write_io_with_capacity<W, F>(writer: &mut W, capacity: usize, f: F) -> Result<(), TableError>
+where
+    W: io::Write,
+    F: FnOnce(&mut String) -> Result<(), TableError>,
+{
+    let mut buffer = String::with_capacity(capacity);
+    f(&mut buffer)?;
+    writer.write_all(buffer.as_bytes())?;
+    Ok(())
+}
+
+/// Get the width of a table rendered with the given options, borders included.
+fn table_width(lengths: &[usize], options: &Options) -> usize {
+    if options.style.borders {
+        let groups = (0..lengths.len().saturating_sub(1))
+            .filter(|&idx| options.ends_group(idx))
+            .count();
+        let edges = if options.outer_borders() { 0 } else { 2 };
+        (compute_total_width(lengths, options.padding) + groups).saturating_sub(edges)
+    } else {
+        let gaps = lengths.len().saturating_sub(1) * options.style.column_gap;
+        lengths.iter().sum::<usize>() + gaps
+    }
+}
+
+/// Get the number of lines a row takes up.
+fn row_height(row: &[String]) -> usize {
+    cmp::max(
+        row.iter()
+            .map(|cell| cell.lines().count())
+            .max()
+            .unwrap_or(0),
+        1,
+    )
+}
+
+/// Render the title above a table, centered across its width
+fn render_title_line<W: fmt::Write>(
+    writer: &mut W,
+    lengths: &[usize],
+    options: &Options,
+    title: &str,
+) -> fmt::Result {
+    let extra = table_width(lengths, options).saturating_sub(options.text_width(title));
+    write_spaces(writer, extra / 2)?;
+    write!(writer, "{}{}", title, options.line_ending)
+}
+
+/// Render a border line, using `horiz` for the horizontal parts
+fn render_border_line<W: fmt::Write>(
+    writer: &mut W,
+    lengths: &[usize],
+    options: &Options,
+    horiz: char,
+    line: BorderLine,
+) -> fmt::Result {
+    let style = &options.style;
+    if lengths.is_empty() || lengths[0] == 0 || !style.borders {
+        return Ok(());
+    }
+    if options.border_mode == BorderMode::VerticalOnly {
+        return Ok(());
+    }
+    if !options.outer_borders() && line != BorderLine::Middle {
+        return Ok(());
+    }
+    let (left, junction, right) = style.junctions(line);
+    if options.outer_borders() {
+        writer.write_char(left)?;
+    }
+    for (idx, len) in lengths.iter().enumerate() {
+        if idx > 0 {
+            writer.write_char(junction)?;
+            if options.ends_group(idx - 1) {
+                writer.write_char(junction)?;
+            }
+        }
+        for _ in 0..(*len + 2 * options.padding) {
+            writer.write_char(horiz)?;
+        }
+    }
+    if options.outer_borders() {
+        writer.write_char(right)?;
+    }
+    write!(writer, "{}", options.line_ending)
+}
+
+/// Render a text line
+fn render_text_line<W: fmt::Write>(
+    writer: &mut W,
+    lengths: &[usize],
+    options: &Options,
+    row: &[String],
+) -> fmt::Result {
+    render_coloured_text_line(writer, lengths, options, row, None)
+}
+
+/// Render a text line, with each cell wrapped in a `colour` prefix and suffix, like an ANSI
+/// colour code and a reset
+///
+/// Only the cells are coloured, not the borders between them.
+fn render_coloured_text_line<W: fmt::Write>(
+    writer: &mut W,
+    lengths: &[usize],
+    options: &Options,
+    row: &[String],
+    colour: Option<(&str, &str)>,
+) -> fmt::Result {
+    let style = &options.style;
+    if lengths.is_empty() || lengths[0] == 0 {
+        return Ok(());
+    }
+    // a cell with multiple lines makes the whole row taller.
+    let cells = row
+        .iter()
+        .map(|cell| cell.lines().collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+    let height = row_height(row);
+    // without borders there's no padding, just a gap between columns.
+    let padding = if style.borders { options.padding } else { 0 };
+    let outer_borders = style.borders && options.outer_borders();
+    for line_idx in 0..height {
+        if outer_borders {
+            writer.write_char(style.vertical)?;
+        }
+        for (idx, (cell, len)) in cells.iter().zip(lengths.iter()).enumerate() {
+            if !style.borders && idx > 0 {
+                write_spaces(writer, style.column_gap)?;
+            }
+            let line = cell.get(line_idx).cloned().unwrap_or("");
+            // the content may be wider than the column if the widths were given up front.
+            let extra = len.saturating_sub(options.text_width(line));
+            let (before, after) = match options.alignment(idx) {
+                Alignment::Left => (0, extra),
+                Alignment::Right => (extra, 0),
+                Alignment::Center => (extra / 2, extra - extra / 2),
+            };
+            if let Some((prefix, _)) = colour {
+                writer.write_str(prefix)?;
+            }
+            write_spaces(writer, before + padding)?;
+            write!(writer, "{}", line)?;
+            let last = idx + 1 == cells.len();
+            if outer_borders || !last {
+                write_spaces(writer, after + padding)?;
+            }
+            // without an outer border, don't leave trailing whitespace after the last column.
+            if let Some((_, suffix)) = colour {
+                writer.write_str(suffix)?;
+            }
+            if style.borders && (outer_borders || !last) {
+                writer.write_char(style.vertical)?;
+                if !last && options.ends_group(idx) {
+                    writer.write_char(style.vertical)?;
+                }
+            }
+        }
+        write!(writer, "{}", options.line_ending)?;
+    }
+
+    Ok(())
+}
+
+/// Write `count` spaces
+fn write_spaces<W: fmt::Write>(writer: &mut W, count: usize) -> fmt::Result {
+    for _ in 0..count {
+        write!(writer, "{}", SPACE_STR)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+
+    #[test]
+    fn render() {
+        let tables = vec![
+            (vec![], &b""[..]),
+            (vec![vec![]], &b""[..]),
+            (
+                vec![vec!["single", "line", "a"], vec!["second", "lines", "a"]],
+                &b"\
++--------+-------+---+
+| single | line  | a |
++--------+-------+---+
+| second | lines | a |
++--------+-------+---+
+"[..],
+            ),
+        ];
+        for (table, result) in tables {
+            let mut out = Vec::new();
+            super::render(&mut out, &table).unwrap();
+            assert_eq!(out, result, "{:#?}", table);
+        }
+    }
+
+    #[test]
+    fn render_to_string() {
+        let tables = vec![
+            (vec![], ""),
+            (vec![vec![]], ""),
+            (
+                vec![vec!["single", "line", "a"], vec!["second", "lines", "a"]],
+                "\
++--------+-------+---+
+| single | line  | a |
++--------+-------+---+
+| second | lines | a |
++--------+-------+---+
+",
+            ),
+        ];
+        for (table, result) in tables {
+            assert_eq!(super::render_to_string(&table), result, "{:#?}", table);
+        }
+    }
+
+    #[test]
+    fn estimate_buffer_size() {
+        let big = (0..200)
+            .map(|row| (0..20).map(|col| (row * col).to_string()).collect())
+            .collect::<Vec<Vec<_>>>();
+        let link = ::HyperLink {
+            url: "https://example.com".to_string(),
+            label: "x".to_string(),
+        };
+        let tables = vec![
+            vec![],
+            vec![vec!["a".to_string()]],
+            vec![vec!["two\nlines".to_string(), "b".to_string()]; 3],
+            vec![vec!["".to_string(); 4]; 2],
+            big,
+            // multi-byte characters take up more bytes than columns.
+            vec![
+                vec!["中文".to_string(), "e\u{301}e\u{301}".to_string()],
+                vec!["a".to_string(), "ünïcödé".to_string()],
+            ],
+            vec![
+                vec![link.to_string(), "😀".to_string()],
+                vec!["abc".to_string(), "".to_string()],
+            ],
+        ];
+        for data in &tables {
+            let estimate = super::table_from_data(data).estimate_size();
+            let out = super::render_to_vec(data);
+            assert!(estimate >= out.len(), "{} < {}", estimate, out.len());
+        }
+    }
+
+    #[test]
+    fn render_fmt() {
+        use std::fmt;
+
+        struct Wrapper(Vec<Vec<&'static str>>);
+
+        impl fmt::Display for Wrapper {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                super::render_fmt(f, &self.0).map_err(|_| fmt::Error)
+            }
+        }
+
+        let table = vec![vec!["single", "line", "a"], vec!["second", "lines", "a"]];
+        let mut out = String::new();
+        super::render_fmt(&mut out, &table).unwrap();
+        assert_eq!(out, super::render_to_string(&table));
+        assert_eq!(format!("{}", Wrapper(table)), out);
+    }
+
+    #[test]
+    fn render_io_error() {
+        use std::io;
+
+        struct Failing;
+
+        impl io::Write for Failing {
+            fn write(&mut self, _: &[u8]) -> io::Result<usize> {
+                Err(io::Error::new(io::ErrorKind::BrokenPipe, "closed"))
+            }
+            fn flush(&mut self) -> io::Result<()> {
+                Ok(())
+            }
+        }
+
+        match super::render(&mut Failing, [["a"]]) {
+            Err(super::TableError::Io(ref e)) if e.kind() == io::ErrorKind::BrokenPipe => (),
+            other => panic!("unexpected result {:?}", other),
+        }
+    }
+
+    #[test]
+    fn render_with_style() {
+        use super::Style;
+
+        let table = vec![vec!["a", "bb"], vec!["ccc", "d"]];
+        let styles = vec![
+            (
+                Style::ascii(),
+                "\
++-----+----+
+| a   | bb |
++-----+----+
+| ccc | d  |
++-----+----+
+",
+            ),
+            (
+                Style::unicode(),
+                "\
+┼─────┼────┼
+│ a   │ bb │
+┼─────┼────┼
+│ ccc │ d  │
+┼─────┼────┼
+",
+            ),
+            (
+                Style::markdown(),
+                "\
+|-----|----|
+| a   | bb |
+|-----|----|
+| ccc | d  |
+|-----|----|
+",
+            ),
+        ];
+        for (style, result) in styles {
+            let mut out = Vec::new();
+            super::render_with_style(&mut out, &table, &style).unwrap();
+            assert_eq!(::std::str::from_utf8(&out).unwrap(), result, "{:?}", style);
+        }
+    }
+
+    #[test]
+    fn render_with_options() {
+        use super::{Alignment, Options};
+
+        let table = vec![
+            vec!["left", "right", "center"],
+            vec!["a", "b", "c"],
+            vec!["dd", "ee", "ff"],
+        ];
+        let options = Options {
+            alignments: vec![Alignment::Left, Alignment::Right, Alignment::Center],
+            ..Options::default()
+        };
+        let mut out = Vec::new();
+        super::render_with_options(&mut out, &table, &options).unwrap();
+        assert_eq!(
+            ::std::str::from_utf8(&out).unwrap(),
+            "\
++------+-------+--------+
+| left | right | center |
++------+-------+--------+
+| a    |     b |   c    |
++------+-------+--------+
+| dd   |    ee |   ff   |
++------+-------+--------+
+"
+        );
+
+        // missing alignments fall back to left
+        let options = Options {
+            alignments: vec![Alignment::Right],
+            ..Options::default()
+        };
+        let mut out = Vec::new();
+        super::render_with_options(&mut out, &table, &options).unwrap();
+        assert_eq!(
+            ::std::str::from_utf8(&out).unwrap().lines().nth(3),
+            Some("|    a | b     | c      |")
+        );
+    }
+
+    #[test]
+    fn unequal_row_lengths() {
+        use super::TableError;
+
+        let table = vec![vec!["a", "b"], vec!["c", "d"], vec!["e"]];
+        let mut out = Vec::new();
+        match super::render(&mut out, &table) {
+            Err(TableError::UnequalRowLengths {
+                expected: 2,
+                got: 1,
+                row_index: 2,
+            }) => (),
+            other => panic!("unexpected result {:?}", other),
+        }
+        assert!(out.is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "rows must be the same length")]
+    fn render_to_string_unequal_row_lengths() {
+        super::render_to_string(vec![vec!["a", "b"], vec!["c"]]);
+    }
+
+    #[test]
+    fn render_to_vec() {
+        let table = vec![vec!["single", "中文"], vec!["second", "a"]];
+        let out = super::render_to_vec(&table);
+        assert!(::std::str::from_utf8(&out).is_ok());
+        assert_eq!(out, super::render_to_string(&table).into_bytes());
+    }
+
+    #[test]
+    #[should_panic(expected = "rows must be the same length")]
+    fn render_to_vec_unequal_row_lengths() {
+        super::render_to_vec(vec![vec!["a", "b"], vec!["c"]]);
+    }
+
+    #[test]
+    fn render_with_header() {
+        let tables = vec![
+            (vec![], ""),
+            (
+                vec![vec!["name", "value"]],
+                "\
++------+-------+
+| name | value |
++======+=======+
+",
+            ),
+            (
+                vec![vec!["name", "value"], vec!["a", "1"], vec!["b", "2"]],
+                "\
++------+-------+
+| name | value |
++======+=======+
+| a    | 1     |
++------+-------+
+| b    | 2     |
++------+-------+
+",
+            ),
+        ];
+        for (table, result) in tables {
+            let mut out = Vec::new();
+            super::render_with_header(&mut out, &table).unwrap();
+            assert_eq!(::std::str::from_utf8(&out).unwrap(), result, "{:#?}", table);
+        }
+    }
+
+    #[test]
+    fn multi_line_cells() {
+        use super::{Alignment, Options};
+
+        let tables = vec![
+            (
+                vec![vec!["one\ntwo", "a"], vec!["b", "c"]],
+                "\
++-----+---+
+| one | a |
+| two |   |
++-----+---+
+| b   | c |
++-----+---+
+",
+            ),
+            (
+                vec![vec!["one\ntwo\nthree", "four\nfive"]],
+                "\
++-------+------+
+| one   | four |
+| two   | five |
+| three |      |
++-------+------+
+",
+            ),
+        ];
+        for (table, result) in tables {
+            assert_eq!(super::render_to_string(&table), result, "{:#?}", table);
+        }
+
+        let table = vec![vec!["a\nbbbb", "c\ndddd", "e\nfffff"]];
+        let options = Options {
+            alignments: vec![Alignment::Left, Alignment::Right, Alignment::Center],
+            ..Options::default()
+        };
+        let mut out = Vec::new();
+        super::render_with_options(&mut out, &table, &options).unwrap();
+        assert_eq!(
+            ::std::str::from_utf8(&out).unwrap(),
+            "\
++------+------+-------+
+| a    |    c |   e   |
+| bbbb | dddd | fffff |
++------+------+-------+
+"
+        );
+    }
+
+    #[test]
+    fn max_column_widths() {
+        use super::Options;
+
+        let cell = "aaaaa bbbb cccc dddd eeee ffff gggg hhhh";
+        assert_eq!(cell.len(), 40);
+        let table = vec![vec![cell, "x"], vec!["short", "y"]];
+        let options = Options {
+            max_column_widths: vec![Some(10)],
+            ..Options::default()
+        };
+        let mut out = Vec::new();
+        super::render_with_options(&mut out, &table, &options).unwrap();
+        assert_eq!(
+            ::std::str::from_utf8(&out).unwrap(),
+            "\
++------------+---+
+| aaaaa bbbb | x |
+| cccc dddd  |   |
+| eeee ffff  |   |
+| gggg hhhh  |   |
++------------+---+
+| short      | y |
++------------+---+
+"
+        );
+    }
+
+    #[cfg(feature = "unicode")]
+    #[test]
+    fn unicode_widths() {
+        let tables = vec![
+            (
+                vec![vec!["中文", "x"], vec!["ab", "y"]],
+                "\
++------+---+
+| 中文 | x |
++------+---+
+| ab   | y |
++------+---+
+",
+            ),
+            (
+                vec![vec!["ascii"], vec!["only"]],
+                "\
++-------+
+| ascii |
++-------+
+| only  |
++-------+
+",
+            ),
+            (
+                vec![vec!["a中b"], vec!["abcde"], vec!["漢字"]],
+                "\
++-------+
+| a中b  |
++-------+
+| abcde |
++-------+
+| 漢字  |
++-------+
+",
+            ),
+        ];
+        for (table, result) in tables {
+            assert_eq!(super::render_to_string(&table), result, "{:#?}", table);
+        }
+    }
+
+    #[test]
+    fn render_iter() {
+        let numbers = (1..4).map(|n| vec![n, n * n]);
+        let mut out = Vec::new();
+        super::render_iter(&mut out, numbers).unwrap();
+        assert_eq!(
+            out,
+            super::render_to_string([[1, 1], [2, 4], [3, 9]]).into_bytes()
+        );
+
+        let mut out = Vec::new();
+        super::render_iter(&mut out, Vec::<Vec<u8>>::new()).unwrap();
+        assert!(out.is_empty());
+    }
+
+    #[test]
+    fn render_rst() {
+        let table = vec![
+            vec!["Header 1", "Header 2"],
+            vec!["body row 1", "column 2"],
+            vec!["body row 2", "multi\nline"],
+        ];
+        let mut out = Vec::new();
+        super::render_rst(&mut out, &table).unwrap();
+        assert_eq!(
+            ::std::str::from_utf8(&out).unwrap(),
+            "\
++------------+----------+
+| Header 1   | Header 2 |
++============+==========+
+| body row 1 | column 2 |
++------------+----------+
+| body row 2 | multi    |
+|            | line     |
++------------+----------+
+"
+        );
+    }
+
+    #[test]
+    fn plain_style() {
+        use super::{Alignment, Options, Style};
+
+        let table = vec![
+            vec!["name", "size", "kind"],
+            vec!["a.txt", "10", "file"],
+            vec!["dir", "4096", "directory"],
+        ];
+        let options = Options {
+            style: Style::plain(2),
+            alignments: vec![Alignment::Left, Alignment::Right],
+            ..Options::default()
+        };
+        let mut out = Vec::new();
+        super::render_with_options(&mut out, &table, &options).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        assert_eq!(
+            out,
+            "\
+name   size  kind
+a.txt    10  file
+dir    4096  directory
+"
+        );
+        assert!(!out.contains(['|', '+', '-']));
+    }
+
+    #[test]
+    fn plain_column_gap() {
+        use {Options, Style};
+
+        let render = |data: &[Vec<&str>], column_gap| {
+            let options = Options {
+                style: Style::plain(column_gap),
+                ..Options::default()
+            };
+            let mut out = Vec::new();
+            super::render_with_options(&mut out, data, &options).unwrap();
+            String::from_utf8(out).unwrap()
+        };
+        let outputs = vec![
+            (render(&[vec!["one"], vec!["three"]], 2), "one\nthree\n"),
+            (
+                render(&[vec!["a", "bb"], vec!["ccc", "d"]], 1),
+                "a   bb\nccc d\n",
+            ),
+            (
+                render(&[vec!["a", "bb"], vec!["ccc", "d"]], 4),
+                "a      bb\nccc    d\n",
+            ),
+        ];
+        for (out, expected) in outputs {
+            assert_eq!(out, expected);
+            assert!(!out.contains(['|', '+', '-']));
+        }
+    }
+
+    #[test]
+    fn padding() {
+        use super::Options;
+
+        let table = vec![vec!["a", "bb"], vec!["ccc", "d"]];
+        let tests = vec![
+            (0, "+---+--+\n|a  |bb|\n+---+--+\n|ccc|d |\n+---+--+\n"),
+            (
+                1,
+                "+-----+----+\n| a   | bb |\n+-----+----+\n| ccc | d  |\n+-----+----+\n",
+            ),
+            (
+                3,
+                "\
++---------+--------+
+|   a     |   bb   |
++---------+--------+
+|   ccc   |   d    |
++---------+--------+
+",
+            ),
+        ];
+        for (padding, result) in tests {
+            let options = Options {
+                padding,
+                ..Options::default()
+            };
+            let mut out = Vec::new();
+            super::render_with_options(&mut out, &table, &options).unwrap();
+            assert_eq!(::std::str::from_utf8(&out).unwrap(), result, "{}", padding);
+        }
+    }
+
+    #[test]
+    fn render_with_row_numbers() {
+        let table = (0..10).map(|n| vec![n * n]).collect::<Vec<_>>();
+        let mut out = Vec::new();
+        super::render_with_row_numbers(&mut out, &table, 1).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        let lines = out.lines().collect::<Vec<_>>();
+        assert_eq!(lines.len(), 21);
+        assert_eq!(lines[0], "+----+----+");
+        assert_eq!(lines[1], "| 1  | 0  |");
+        assert_eq!(lines[17], "| 9  | 64 |");
+        assert_eq!(lines[19], "| 10 | 81 |");
+    }
+
+    #[test]
+    fn transpose() {
+        let table = vec![vec!["corner", "b", "c"], vec!["d", "e", "f"]];
+        assert_eq!(
+            super::transpose(&table).unwrap(),
+            vec![vec!["corner", "d"], vec!["b", "e"], vec!["c", "f"]]
+        );
+        let mut out = Vec::new();
+        super::render_transposed(&mut out, &table).unwrap();
+        assert_eq!(
+            ::std::str::from_utf8(&out).unwrap(),
+            "\
++--------+---+
+| corner | d |
++--------+---+
+| b      | e |
++--------+---+
+| c      | f |
++--------+---+
+"
+        );
+        assert!(super::transpose(vec![vec![1, 2], vec![3]]).is_err());
+        assert!(super::transpose(Vec::<Vec<u8>>::new()).unwrap().is_empty());
+    }
+
+    #[test]
+    fn min_column_widths() {
+        use super::Options;
+
+        let table = vec![vec!["hi", "already wide"]];
+        let options = Options {
+            min_column_widths: vec![10, 5],
+            ..Options::default()
+        };
+        let mut out = Vec::new();
+        super::render_with_options(&mut out, &table, &options).unwrap();
+        assert_eq!(
+            ::std::str::from_utf8(&out).unwrap(),
+            "\
++------------+--------------+
+| hi         | already wide |
++------------+--------------+
+"
+        );
+    }
+
+    #[test]
+    fn column_policies() {
+        use super::{ColumnWidthPolicy, Options, Overflow};
+
+        let options = Options {
+            column_policies: vec![
+                ColumnWidthPolicy::Auto,
+                ColumnWidthPolicy::Fixed(4),
+                ColumnWidthPolicy::Min(10),
+                ColumnWidthPolicy::Max(3),
+                ColumnWidthPolicy::MinMax(2, 5),
+                ColumnWidthPolicy::Fixed(10),
+            ],
+            overflow: Overflow::Truncate,
+            ..Options::default()
+        };
+        let mut out = Vec::new();
+        super::render_with_options(&mut out, [["abcdefgh"; 6]], &options).unwrap();
+        assert_eq!(
+            ::std::str::from_utf8(&out).unwrap(),
+            "\
++----------+------+------------+-----+-------+------------+
+| abcdefgh | abc… | abcdefgh   | ab… | abcd… | abcdefgh   |
++----------+------+------------+-----+-------+------------+
+"
+        );
+
+        // the narrowest maximum and the widest minimum are used.
+        let options = Options {
+            column_policies: vec![ColumnWidthPolicy::Max(6), ColumnWidthPolicy::Min(3)],
+            max_column_widths: vec![Some(4)],
+            min_column_widths: vec![0, 5],
+            overflow: Overflow::Truncate,
+            ..Options::default()
+        };
+        assert_eq!(
+            super::compute_dimensions(&[["abcdefgh", "a"]], &options),
+            (1 + 4 + 3 + 5 + 3, 3)
+        );
+    }
+
+    #[test]
+    fn truncation() {
+        use super::{Options, Overflow};
+
+        let table = vec![vec!["a long piece of text", "short"]];
+        let options = Options {
+            max_column_widths: vec![Some(10), Some(5)],
+            overflow: Overflow::Truncate,
+            ..Options::default()
+        };
+        let mut out = Vec::new();
+        super::render_with_options(&mut out, &table, &options).unwrap();
+        assert_eq!(
+            ::std::str::from_utf8(&out).unwrap(),
+            "\
++------------+-------+
+| a long pi… | short |
++------------+-------+
+"
+        );
+    }
+
+    #[test]
+    fn strip_ansi_for_width() {
+        let table = vec![vec!["\x1b[31mred\x1b[0m", "1"], vec!["plain", "2"]];
+        let options = ::Options {
+            strip_ansi_for_width: true,
+            ..::Options::default()
+        };
+        let mut out = Vec::new();
+        super::render_with_options(&mut out, &table, &options).unwrap();
+        assert_eq!(
+            ::std::str::from_utf8(&out).unwrap(),
+            "\
++-------+---+
+| \x1b[31mred\x1b[0m   | 1 |
++-------+---+
+| plain | 2 |
++-------+---+
+"
+        );
+    }
+
+    #[test]
+    fn strip_ansi_with_max_widths() {
+        use super::{Options, Overflow};
+
+        let red = "\x1b[31mred red red\x1b[0m";
+        let table = vec![vec![red, "1"], vec!["2", red]];
+        let render = |overflow| {
+            let options = Options {
+                strip_ansi_for_width: true,
+                max_column_widths: vec![Some(4), Some(4)],
+                overflow,
+                ..Options::default()
+            };
+            let mut out = Vec::new();
+            super::render_with_options(&mut out, &table, &options).unwrap();
+            String::from_utf8(out).unwrap()
+        };
+        let red = "\x1b[31mred\x1b[0m";
+        assert_eq!(
+            render(Overflow::Wrap),
+            format!(
+                "\
++-----+-----+
+| {0} | 1   |
+| {0} |     |
+| {0} |     |
++-----+-----+
+| 2   | {0} |
+|     | {0} |
+|     | {0} |
++-----+-----+
+",
+                red
+            )
+        );
+        assert_eq!(
+            render(Overflow::Truncate),
+            format!(
+                "\
++------+------+
+| {0}… | 1    |
++------+------+
+| 2    | {0}… |
++------+------+
+",
+                red
+            )
+        );
+    }
+
+    #[test]
+    fn render_centered_in_width() {
+        let centered = |total_width: usize| {
+            let mut out = Vec::new();
+            super::render_centered_in_width(&mut out, [["ab", "c"]], total_width).unwrap();
+            String::from_utf8(out).unwrap()
+        };
+        // the table is 10 wide.
+        let table = ["+----+---+", "| ab | c |", "+----+---+"];
+        let indented = |indent: &str| {
+            table
+                .iter()
+                .map(|line| format!("{}{}\n", indent, line))
+                .collect::<String>()
+        };
+        assert_eq!(centered(16), indented("   "));
+        assert_eq!(centered(17), indented("   "));
+        assert_eq!(centered(13), indented(" "));
+        assert_eq!(centered(10), indented(""));
+        assert_eq!(centered(4), indented(""));
+    }
+
+    #[test]
+    fn render_side_by_side() {
+        let side_by_side = |left: &[Vec<&str>], right: &[Vec<&str>]| {
+            let mut out = Vec::new();
+            super::render_side_by_side(&mut out, left, right, 2).unwrap();
+            String::from_utf8(out).unwrap()
+        };
+        let before = vec![vec!["a", "1"], vec!["b", "2"]];
+        let after = vec![vec!["a", "10"], vec!["b", "2"]];
+        assert_eq!(
+            side_by_side(&before, &after),
+            "\
++---+---+  +---+----+
+| a | 1 |  | a | 10 |
++---+---+  +---+----+
+| b | 2 |  | b | 2  |
++---+---+  +---+----+
+"
+        );
+
+        let short = vec![vec!["x"]];
+        assert_eq!(
+            side_by_side(&short, &before),
+            "\
++---+  +---+---+
+| x |  | a | 1 |
++---+  +---+---+
+       | b | 2 |
+       +---+---+
+"
+        );
+        assert_eq!(
+            side_by_side(&before, &short),
+            "\
++---+---+  +---+
+| a | 1 |  | x |
++---+---+  +---+
+| b | 2 |
++---+---+
+"
+        );
+    }
+
+    #[test]
+    fn title() {
+        let render_title = |data: &[Vec<&str>], title: &str| {
+            let options = ::Options {
+                title: Some(title.to_string()),
+                ..::Options::default()
+            };
+            let mut out = Vec::new();
+            super::render_with_options(&mut out, data, &options).unwrap();
+            String::from_utf8(out).unwrap()
+        };
+        // an even total width. When the space can't be split evenly, the extra goes on the right.
+        let even = vec![vec!["a", "1"], vec!["bb", "2"]];
+        assert_eq!(
+            render_title(&even, "odd"),
+            "   odd\n+----+---+\n| a  | 1 |\n+----+---+\n| bb | 2 |\n+----+---+\n"
+        );
+        assert_eq!(
+            render_title(&even, "even"),
+            "   even\n+----+---+\n| a  | 1 |\n+----+---+\n| bb | 2 |\n+----+---+\n"
+        );
+        // an odd total width.
+        let odd = vec![vec!["abc", "1"]];
+        assert_eq!(
+            render_title(&odd, "odd"),
+            "    odd\n+-----+---+\n| abc | 1 |\n+-----+---+\n"
+        );
+        assert_eq!(
+            render_title(&odd, "ab"),
+            "    ab\n+-----+---+\n| abc | 1 |\n+-----+---+\n"
+        );
+        assert_eq!(
+            render_title(&[vec!["a"]], "too wide"),
+            "too wide\n+---+\n| a |\n+---+\n"
+        );
+    }
+
+    #[test]
+    fn tables_equal() {
+        use super::tables_equal;
+
+        let table = vec![vec!["a", "b"], vec!["c", "d"]];
+        assert!(tables_equal(&table, table.clone()));
+        assert!(tables_equal(&table, &[vec!["a", "b"], vec!["c", "d"]]));
+        assert!(!tables_equal(&table, &[vec!["a", "b"], vec!["c", "e"]]));
+        assert!(!tables_equal(&table, &[vec!["a", "b"]]));
+        assert!(!tables_equal(&table, &[]));
+    }
+
+    #[test]
+    fn render_with_separator_rows() {
+        let with_separators = |separator_after: &[usize]| {
+            let data = vec![vec!["a", "1"], vec!["b", "2"], vec!["c", "3"]];
+            let mut out = Vec::new();
+            super::render_with_separator_rows(&mut out, &data, separator_after).unwrap();
+            String::from_utf8(out).unwrap()
+        };
+        assert_eq!(
+            with_separators(&[0]),
+            "\
++---+---+
+| a | 1 |
++---+---+
+|   |   |
++---+---+
+| b | 2 |
++---+---+
+| c | 3 |
++---+---+
+"
+        );
+        assert_eq!(with_separators(&[2]), with_separators(&[]));
+        assert_eq!(
+            with_separators(&[0, 1]),
+            "\
++---+---+
+| a | 1 |
++---+---+
+|   |   |
++---+---+
+| b | 2 |
++---+---+
+|   |   |
++---+---+
+| c | 3 |
++---+---+
+"
+        );
+    }
+
+    #[test]
+    fn crlf_line_endings() {
+        let options = ::Options {
+            line_ending: ::LineEnding::CrLf,
+            title: Some("t".to_string()),
+            ..::Options::default()
+        };
+        let mut out = Vec::new();
+        super::render_with_options(&mut out, [["a", "b"], ["c", "d"]], &options).unwrap();
+        assert_eq!(
+            ::std::str::from_utf8(&out).unwrap(),
+            "    t\r\n+---+---+\r\n| a | b |\r\n+---+---+\r\n| c | d |\r\n+---+---+\r\n"
+        );
+    }
+
+    #[test]
+    fn io_and_fmt_match() {
+        let tables = vec![
+            vec![],
+            vec![vec!["a"]],
+            vec![vec!["multi\nline", "中文"], vec!["x", "y"]],
+        ];
+        let options = vec![
+            ::Options::default(),
+            ::Options {
+                style: ::Style::unicode(),
+                header_row: true,
+                border_mode: ::BorderMode::Compact,
+                ..::Options::default()
+            },
+            ::Options {
+                style: ::Style::plain(2),
+                title: Some("title".to_string()),
+                ..::Options::default()
+            },
+        ];
+        for data in &tables {
+            let mut io_out = Vec::new();
+            super::render(&mut io_out, data).unwrap();
+            let mut fmt_out = String::new();
+            super::render_fmt(&mut fmt_out, data).unwrap();
+            assert_eq!(String::from_utf8(io_out).unwrap(), fmt_out);
+
+            let table = data.iter().collect::<::Table>();
+            for options in &options {
+                let mut io_out = Vec::new();
+                table.render_with_options(&mut io_out, options).unwrap();
+                let mut fmt_out = String::new();
+                table.write_table(&mut fmt_out, options).unwrap();
+                assert_eq!(String::from_utf8(io_out).unwrap(), fmt_out);
+            }
+        }
+    }
+
+    #[test]
+    fn render_truncated() {
+        let truncated = |max_width| {
+            let data = vec![vec!["aa", "bb", "cc"], vec!["1", "2", "3"]];
+            let mut out = Vec::new();
+            super::render_truncated(&mut out, &data, max_width).unwrap();
+            String::from_utf8(out).unwrap()
+        };
+        // the full table is 16 wide.
+        assert_eq!(
+            truncated(16),
+            "\
++----+----+----+
+| aa | bb | cc |
++----+----+----+
+| 1  | 2  | 3  |
++----+----+----+
+"
+        );
+        assert_eq!(
+            truncated(15),
+            "\
++----+----+---+
+| aa | bb | … |
++----+----+---+
+| 1  | 2  | … |
++----+----+---+
+"
+        );
+        let only_marker = "+---+\n| … |\n+---+\n| … |\n+---+\n";
+        assert_eq!(truncated(8), only_marker);
+        assert_eq!(truncated(5), only_marker);
+        assert_eq!(truncated(0), only_marker);
+    }
+
+    #[test]
+    fn shrink_widths() {
+        let tests = vec![
+            (vec![], 0, vec![]),
+            (vec![3, 4], 7, vec![3, 4]),
+            (vec![3, 4], 10, vec![3, 4]),
+            (vec![10, 10], 10, vec![5, 5]),
+            (vec![10, 5], 6, vec![4, 2]),
+            // the narrow column can't go below 1, so the wide one gets the rest.
+            (vec![20, 1], 5, vec![4, 1]),
+            (vec![0, 8], 4, vec![0, 4]),
+            (vec![5, 5, 5], 2, vec![1, 1, 1]),
+        ];
+        for (widths, budget, shrunk) in tests {
+            assert_eq!(
+                super::shrink_widths(&widths, budget),
+                shrunk,
+                "{:?}",
+                widths
+            );
+        }
+    }
+
+    #[test]
+    fn render_with_proportional_shrink() {
+        let shrink = |data: &[Vec<String>], max_width| {
+            let mut out = Vec::new();
+            super::render_with_proportional_shrink(&mut out, data, max_width).unwrap();
+            String::from_utf8(out).unwrap()
+        };
+        let small = vec![vec!["a".to_string(), "b".to_string()]];
+        assert_eq!(shrink(&small, 9), super::render_to_string(&small));
+
+        let wide = vec![(0..10)
+            .map(|idx| format!("column number {}", idx))
+            .collect::<Vec<_>>()];
+        let out = shrink(&wide, 80);
+        for line in out.lines() {
+            assert_eq!(line.chars().count(), 80, "{}", line);
+        }
+        assert!(out.contains("| colu… | col… |"), "{}", out);
+    }
+
+    #[test]
+    fn compute_dimensions() {
+        use {BorderMode, Options, Style};
+
+        let tables = vec![
+            vec![],
+            vec![vec!["a"]],
+            vec![
+                vec!["head", "er"],
+                vec!["multi\nline", "中文"],
+                vec!["x", "y"],
+            ],
+            vec![vec!["a long cell that wraps", "b"], vec!["c", "d"]],
+        ];
+        let options = vec![
+            Options::default(),
+            Options {
+                header_row: true,
+                footer_row: true,
+                border_mode: BorderMode::Compact,
+                padding: 2,
+                ..Options::default()
+            },
+            Options {
+                style: Style::plain(2),
+                title: Some("title".to_string()),
+                ..Options::default()
+            },
+            Options {
+                max_column_widths: vec![Some(6)],
+                min_column_widths: vec![0, 4],
+                footer_row: true,
+                ..Options::default()
+            },
+            Options {
+                style: Style::psql(),
+                header_row: true,
+                ..Options::default()
+            },
+            Options {
+                header_row: true,
+                border_mode: BorderMode::InnerOnly,
+                ..Options::default()
+            },
+            Options {
+                header_row: true,
+                footer_row: true,
+                border_mode: BorderMode::VerticalOnly,
+                ..Options::default()
+            },
+            Options {
+                header_row: true,
+                footer_row: true,
+                border_mode: BorderMode::HeaderOnly,
+                ..Options::default()
+            },
+            Options {
+                border_mode: BorderMode::HeaderOnly,
+                ..Options::default()
+            },
+            Options {
+                border_mode: BorderMode::Compact,
+                row_style: Some(::RowStyleFn::new(|idx| ::RowStyle {
+                    is_separator: idx == 0,
+                    ..::RowStyle::default()
+                })),
+                ..Options::default()
+            },
+            Options {
+                header_separator: true,
+                border_mode: BorderMode::Compact,
+                ..Options::default()
+            },
+            Options {
+                outer_padding_lines: 2,
+                title: Some("title".to_string()),
+                ..Options::default()
+            },
+        ];
+        for data in &tables {
+            for options in &options {
+                let mut out = Vec::new();
+                super::render_with_options(&mut out, data, options).unwrap();
+                let out = String::from_utf8(out).unwrap();
+                let width = out.lines().map(super::str_width).max().unwrap_or(0);
+                let height = out.lines().count();
+                assert_eq!(
+                    super::compute_dimensions(data, options),
+                    (width, height),
+                    "{}",
+                    out
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn inner_only_borders() {
+        use {BorderMode, Options};
+
+        let data = [["a", "b", "c"], ["d", "e", "f"], ["g", "h", "i"]];
+        let render = |border_mode| {
+            let options = Options {
+                border_mode,
+                ..Options::default()
+            };
+            let mut out = Vec::new();
+            super::render_with_options(&mut out, data, &options).unwrap();
+            String::from_utf8(out).unwrap()
+        };
+        let full = render(BorderMode::Full);
+        let inner = render(BorderMode::InnerOnly);
+        assert_eq!(
+            inner,
+            concat!(
+                " a | b | c\n",
+                "---+---+---\n",
+                " d | e | f\n",
+                "---+---+---\n",
+                " g | h | i\n",
+            )
+        );
+        // the same table, with the first and last lines and columns cut off, and no trailing
+        // padding.
+        let lines = full.lines().collect::<Vec<_>>();
+        let trimmed = lines[1..lines.len() - 1]
+            .iter()
+            .map(|line| format!("{}\n", line[1..line.len() - 1].trim_end()))
+            .collect::<String>();
+        assert_eq!(inner, trimmed);
+    }
+
+    #[test]
+    fn vertical_only_borders() {
+        use {BorderMode, Options};
+
+        let options = Options {
+            header_row: true,
+            footer_row: true,
+            border_mode: BorderMode::VerticalOnly,
+            ..Options::default()
+        };
+        let data = [["time", "level"], ["12:00", "info"], ["12:01", "warn"]];
+        let mut out = Vec::new();
+        super::render_with_options(&mut out, data, &options).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        assert_eq!(
+            out,
+            "| time  | level |\n| 12:00 | info  |\n| 12:01 | warn  |\n"
+        );
+        assert!(!out.contains('-') && !out.contains('+') && !out.contains('='));
+    }
+
+    #[test]
+    fn header_only_borders() {
+        use {BorderMode, Options};
+
+        let render = |rows: usize| {
+            let options = Options {
+                header_row: true,
+                border_mode: BorderMode::HeaderOnly,
+                ..Options::default()
+            };
+            let data = ::std::iter::once("n".to_string())
+                .chain((1..rows).map(|n| n.to_string()))
+                .map(|cell| vec![cell])
+                .take(rows)
+                .collect::<Vec<_>>();
+            let mut out = Vec::new();
+            super::render_with_options(&mut out, data, &options).unwrap();
+            String::from_utf8(out).unwrap()
+        };
+        assert_eq!(render(0), "");
+        assert_eq!(render(1), "+---+\n| n |\n+===+\n");
+        assert_eq!(
+            render(5),
+            "+---+\n| n |\n+===+\n| 1 |\n| 2 |\n| 3 |\n| 4 |\n"
+        );
+    }
+
+    #[test]
+    fn header_separator() {
+        use Options;
+
+        let data = [["a", "b"], ["c", "d"], ["e", "f"]];
+        let default = ::render_to_string(data);
+        assert!(!default.contains('='));
+
+        let options = Options {
+            header_separator: true,
+            ..Options::default()
+        };
+        let mut out = Vec::new();
+        super::render_with_options(&mut out, data, &options).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        assert_eq!(
+            out,
+            "\
++---+---+
+| a | b |
++===+===+
+| c | d |
++---+---+
+| e | f |
++---+---+
+"
+        );
+        assert_eq!(out.lines().filter(|line| line.contains('=')).count(), 1);
+
+        // a real header isn't followed by a second line.
+        let options = Options {
+            header_row: true,
+            ..options
+        };
+        let mut with_header = Vec::new();
+        super::render_with_options(&mut with_header, data, &options).unwrap();
+        assert_eq!(String::from_utf8(with_header).unwrap(), out);
+    }
+
+    #[test]
+    fn outer_padding_lines() {
+        use Options;
+
+        let render = |outer_padding_lines| {
+            let options = Options {
+                outer_padding_lines,
+                ..Options::default()
+            };
+            let mut out = Vec::new();
+            super::render_with_options(&mut out, [["a", "b"]], &options).unwrap();
+            String::from_utf8(out).unwrap()
+        };
+        let table = ::render_to_string([["a", "b"]]);
+        assert_eq!(render(0), table);
+        assert_eq!(render(2), format!("\n\n{}\n\n", table));
+    }
+
+    #[test]
+    fn single_write() {
+        use std::io;
+
+        struct CountingWriter(usize);
+
+        impl io::Write for CountingWriter {
+            fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+                self.0 += 1;
+                Ok(buf.len())
+            }
+            fn flush(&mut self) -> io::Result<()> {
+                Ok(())
+            }
+        }
+
+        let data = vec![vec!["a", "b", "c"]; 100];
+        let mut writer = CountingWriter(0);
+        super::render(&mut writer, &data).unwrap();
+        assert_eq!(writer.0, 1);
+        let mut writer = CountingWriter(0);
+        data.iter()
+            .collect::<::Table>()
+            .render_with_options(&mut writer, &::Options::default())
+            .unwrap();
+        assert_eq!(writer.0, 1);
+    }
+
+    #[test]
+    fn zebra_stripe() {
+        let options = ::Options {
+            zebra_stripe: Some(("<e>".to_string(), "<o>".to_string())),
+            header_row: true,
+            ..::Options::default()
+        };
+        let mut out = Vec::new();
+        super::render_with_options(
+            &mut out,
+            [["h", "h"], ["a", "b"], ["c", "d"], ["e", "f"]],
+            &options,
+        )
+        .unwrap();
+        assert_eq!(
+            ::std::str::from_utf8(&out).unwrap(),
+            "\
++---+---+
+| h | h |
++===+===+
+|<e> a \x1b[0m|<e> b \x1b[0m|
++---+---+
+|<o> c \x1b[0m|<o> d \x1b[0m|
++---+---+
+|<e> e \x1b[0m|<e> f \x1b[0m|
++---+---+
+"
+        );
+    }
+
+    #[test]
+    fn row_style() {
+        use {BorderMode, RowStyle, RowStyleFn};
+
+        let green = |idx| match idx % 2 {
+            0 => RowStyle {
+                prefix: "\x1b[32m".to_string(),
+                suffix: "\x1b[0m".to_string(),
+                ..RowStyle::default()
+            },
+            _ => RowStyle::default(),
+        };
+        let options = ::Options {
+            header_row: true,
+            row_style: Some(RowStyleFn::new(green)),
+            ..::Options::default()
+        };
+        let data = [["h", "h"], ["a", "b"], ["c", "d"], ["e", "f"]];
+        let mut out = Vec::new();
+        super::render_with_options(&mut out, data, &options).unwrap();
+        assert_eq!(
+            ::std::str::from_utf8(&out).unwrap(),
+            "\
++---+---+
+| h | h |
++===+===+
+|\x1b[32m a \x1b[0m|\x1b[32m b \x1b[0m|
++---+---+
+| c | d |
++---+---+
+|\x1b[32m e \x1b[0m|\x1b[32m f \x1b[0m|
++---+---+
+"
+        );
+
+        // separators can be added to tables that don't have them.
+        let options = ::Options {
+            border_mode: BorderMode::Compact,
+            row_style: Some(RowStyleFn::new(|idx| RowStyle {
+                is_separator: idx == 1,
+                ..RowStyle::default()
+            })),
+            ..::Options::default()
+        };
+        let mut out = Vec::new();
+        super::render_with_options(&mut out, [["a"], ["b"], ["c"]], &options).unwrap();
+        assert_eq!(
+            ::std::str::from_utf8(&out).unwrap(),
+            "+---+\n| a |\n| b |\n+---+\n| c |\n+---+\n"
+        );
+    }
+
+    #[test]
+    fn column_groups() {
+        let options = ::Options {
+            column_groups: vec![1, 3, 5],
+            ..::Options::default()
+        };
+        let data = [["a", "b", "c", "d", "e", "f"]];
+        let mut out = Vec::new();
+        super::render_with_options(&mut out, data, &options).unwrap();
+        assert_eq!(
+            ::std::str::from_utf8(&out).unwrap(),
+            "\
++---+---++---+---++---+---+
+| a | b || c | d || e | f |
++---+---++---+---++---+---+
+"
+        );
+        assert_eq!(super::compute_dimensions(&data, &options), (27, 3));
+    }
+
+    #[test]
+    fn render_diff() {
+        let diff = |before: &[Vec<&str>], after: &[Vec<&str>], marker| {
+            let mut out = Vec::new();
+            super::render_diff(&mut out, before, after, marker).unwrap();
+            String::from_utf8(out).unwrap()
+        };
+        let before = vec![vec!["a", "1"], vec!["b", "2"]];
+        assert_eq!(
+            diff(&before, &before, "*"),
+            super::render_to_string(&before)
+        );
+        assert_eq!(
+            diff(&before, &[vec!["a", "1"], vec!["b", "3"]], "*"),
+            "\
++---+--------+
+| a | 1      |
++---+--------+
+| b | *2 → 3 |
++---+--------+
+"
+        );
+        assert_eq!(
+            diff(&before, &[vec!["a", "1"], vec!["c", "4"]], "\x1b[33m"),
+            "\
++-------+-------+
+| a     | 1     |
++-------+-------+
+| \x1b[33mb → c\x1b[0m | \x1b[33m2 → 4\x1b[0m |
++-------+-------+
+"
+        );
+    }
+
+    #[test]
+    fn render_psql() {
+        let data = vec![
+            vec!["id", "name", "score"],
+            vec!["1", "alice", "9000"],
+            vec!["2", "bob", "12"],
+        ];
+        let mut out = Vec::new();
+        super::render_psql(&mut out, &data).unwrap();
+        assert_eq!(
+            ::std::str::from_utf8(&out).unwrap(),
+            concat!(
+                " id | name  | score\n",
+                "----+-------+-------\n",
+                " 1  | alice | 9000\n",
+                " 2  | bob   | 12\n",
+            )
+        );
+    }
+
+    #[test]
+    fn column_widths() {
+        let data = vec![vec!["a", "two\nlines"], vec!["二", "b"]];
+        let widths = super::column_widths(&data);
+        let wide = if cfg!(feature = "unicode") { 2 } else { 1 };
+        assert_eq!(widths, vec![wide, 5]);
+        // each column is padded out to its width, plus a space either side.
+        let rendered = ::render_to_string(&data);
+        let border = rendered.lines().next().unwrap();
+        let dashes = border
+            .split('+')
+            .filter(|part| !part.is_empty())
+            .map(|part| part.len() - 2)
+            .collect::<Vec<_>>();
+        assert_eq!(dashes, widths);
+        assert_eq!(
+            super::column_widths(&Vec::<Vec<u8>>::new()),
+            Vec::<usize>::new()
+        );
+    }
+
+    #[test]
+    fn render_border_line() {
+        use style::BorderLine;
+
+        let options = ::Options::default();
+        let border = |horiz: char, line: BorderLine| {
+            let mut out = String::new();
+            super::render_border_line(&mut out, &[1, 3], &options, horiz, line).unwrap();
+            out
+        };
+        assert_eq!(border('-', BorderLine::Top), "+---+-----+\n");
+        assert_eq!(border('=', BorderLine::Middle), "+===+=====+\n");
+        assert_eq!(border('-', BorderLine::Bottom), "+---+-----+\n");
+
+        let mut out = String::new();
+        super::render_border_line(&mut out, &[], &options, '-', BorderLine::Top).unwrap();
+        assert_eq!(out, "");
+
+        // the lines are the same ones `render_with_header` draws.
+        let mut out = Vec::new();
+        ::render_with_header(&mut out, [["a", "bcd"], ["e", "f"]]).unwrap();
+        let rendered = String::from_utf8(out).unwrap();
+        let lines = rendered.lines().collect::<Vec<_>>();
+        assert_eq!(lines[0], border('-', BorderLine::Top).trim_end());
+        assert_eq!(lines[2], border('=', BorderLine::Middle).trim_end());
+    }
+
+    #[test]
+    fn render_byte_count() {
+        let tables = vec![
+            vec![],
+            vec![vec!["a", "b"]],
+            vec![vec!["二", "two\nlines"], vec!["c", "d"]],
+        ];
+        for data in tables {
+            let mut out = Vec::new();
+            let written = super::render(&mut out, &data).unwrap();
+            assert_eq!(written, ::render_to_vec(&data).len());
+        }
+    }
+
+    #[test]
+    fn render_kv() {
+        let kv = |pairs: &[(&str, u32)]| {
+            let mut out = Vec::new();
+            super::render_kv(&mut out, pairs).unwrap();
+            String::from_utf8(out).unwrap()
+        };
+        assert_eq!(
+            kv(&[]),
+            "+-----+-------+\n| Key | Value |\n+=====+=======+\n"
+        );
+        assert_eq!(
+            kv(&[("a", 1)]),
+            "+-----+-------+\n| Key | Value |\n+=====+=======+\n| a   | 1     |\n+-----+-------+\n"
+        );
+        assert_eq!(
+            kv(&[("a longer key", 1234567890), ("b", 2)]),
+            "\
++--------------+------------+
+| Key          | Value      |
++==============+============+
+| a longer key | 1234567890 |
++--------------+------------+
+| b            | 2          |
++--------------+------------+
+"
+        );
+    }
+
+    #[test]
+    fn render_with_footnotes() {
+        let data = [["a[1]", "b"], ["c", "d[2]"]];
+        let footnotes = |footnotes: &[(usize, &str)]| {
+            let mut out = Vec::new();
+            super::render_with_footnotes(&mut out, data, footnotes).unwrap();
+            String::from_utf8(out).unwrap()
+        };
+        let table = ::render_to_string(data);
+        assert_eq!(footnotes(&[]), table);
+        assert_eq!(
+            footnotes(&[(1, "first"), (2, "second")]),
+            format!("{}[1] first\n[2] second\n", table)
+        );
+        let text = format!("measured at {} on {}", "noon", 12);
+        assert_eq!(
+            footnotes(&[(2, &text)]),
+            format!("{}[2] measured at noon on 12\n", table)
+        );
+    }
+
+    #[test]
+    fn render_with_row_groups() {
+        let groups = |groups: &[(Option<&str>, Vec<Vec<&str>>)]| {
+            let mut out = Vec::new();
+            super::render_with_row_groups(&mut out, groups).unwrap();
+            String::from_utf8(out).unwrap()
+        };
+        assert_eq!(
+            groups(&[
+                (Some("small"), vec![vec!["a", "1"], vec!["b", "2"]]),
+                (Some("big"), vec![vec!["elephant", "1000"]]),
+            ]),
+            concat!(
+                "       small\n",
+                "+----------+------+\n",
+                "| a        | 1    |\n",
+                "+----------+------+\n",
+                "| b        | 2    |\n",
+                "+----------+------+\n",
+                "        big\n",
+                "+----------+------+\n",
+                "| elephant | 1000 |\n",
+                "+----------+------+\n",
+            )
+        );
+        assert_eq!(
+            groups(&[
+                (Some("one"), vec![vec!["a"]]),
+                (Some("none"), vec![]),
+                (None, vec![vec!["b"]]),
+            ]),
+            " one\n+---+\n| a |\n+---+\nnone\n\n+---+\n| b |\n+---+\n"
+        );
+        let rows = vec![vec!["a", "b"], vec!["c"]];
+        let mut out = Vec::new();
+        assert!(super::render_with_row_groups(&mut out, &[(None, rows)]).is_err());
+    }
+
+    #[test]
+    fn render_paged() {
+        let paged = |rows: usize, page_size: usize| {
+            let data = ::std::iter::once("n".to_string())
+                .chain((1..=rows).map(|n| n.to_string()))
+                .map(|cell| vec![cell])
+                .collect::<Vec<_>>();
+            let mut out = Vec::new();
+            super::render_paged(&mut out, &data, page_size).unwrap();
+            String::from_utf8(out).unwrap()
+        };
+        let header = "+---+\n| n |\n+===+\n";
+        assert_eq!(
+            paged(2, 10),
+            format!("{}| 1 |\n+---+\n| 2 |\n+---+\n", header)
+        );
+        assert_eq!(
+            paged(2, 1),
+            format!("{0}| 1 |\n+---+\n{0}| 2 |\n+---+\n", header)
+        );
+        assert_eq!(
+            paged(7, 3),
+            format!(
+                "{0}{1}{0}{2}{0}| 7 |\n+---+\n",
+                header,
+                "| 1 |\n+---+\n| 2 |\n+---+\n| 3 |\n+---+\n",
+                "| 4 |\n+---+\n| 5 |\n+---+\n| 6 |\n+---+\n",
+            )
+        );
+        assert_eq!(paged(0, 3), header);
+    }
+
+    #[test]
+    fn render_with_page_breaks() {
+        let paged = |rows: usize, page_height| {
+            let data = ::std::iter::once("n".to_string())
+                .chain((1..=rows).map(|n| n.to_string()))
+                .map(|cell| vec![cell])
+                .collect::<Vec<_>>();
+            let mut out = Vec::new();
+            super::render_with_page_breaks(&mut out, &data, page_height, "\x0c").unwrap();
+            String::from_utf8(out).unwrap()
+        };
+        let header = "+---+\n| n |\n+===+\n";
+        assert_eq!(
+            paged(2, 5),
+            format!("{}| 1 |\n+---+\n| 2 |\n+---+\n", header)
+        );
+        assert_eq!(
+            paged(4, 2),
+            format!(
+                "{0}| 1 |\n+---+\n| 2 |\n+---+\n\x0c{0}| 3 |\n+---+\n| 4 |\n+---+\n",
+                header
+            )
+        );
+        assert_eq!(
+            paged(3, 1),
+            format!(
+                "{0}| 1 |\n+---+\n\x0c{0}| 2 |\n+---+\n\x0c{0}| 3 |\n+---+\n",
+                header
+            )
+        );
+        assert_eq!(paged(3, 1).matches('\x0c').count(), 2);
+        // the pages line up, even though only the last has a two digit number.
+        let pages = paged(10, 9);
+        assert!(pages.starts_with("+----+\n| n  |\n+====+\n| 1  |\n"));
+        assert!(pages.ends_with("+----+\n| n  |\n+====+\n| 10 |\n+----+\n"));
+    }
+
+    #[test]
+    fn render_mysql() {
+        // `SELECT user, host FROM mysql.user;`
+        let data = vec![
+            vec!["user", "host"],
+            vec!["root", "localhost"],
+            vec!["mysql.sys", "localhost"],
+        ];
+        let mut out = Vec::new();
+        super::render_mysql(&mut out, &data).unwrap();
+        assert_eq!(
+            ::std::str::from_utf8(&out).unwrap(),
+            "\
++-----------+-----------+
+| user      | host      |
++-----------+-----------+
+| root      | localhost |
+| mysql.sys | localhost |
++-----------+-----------+
+"
+        );
+
+        // `SELECT 1 AS id, 'alice' AS name UNION SELECT 10, 'bob';` right-aligns the numbers.
+        let options = ::Options {
+            alignments: vec![::Alignment::Right],
+            ..super::mysql_options()
+        };
+        let mut out = Vec::new();
+        super::render_with_options(
+            &mut out,
+            [["id", "name"], ["1", "alice"], ["10", "bob"]],
+            &options,
+        )
+        .unwrap();
+        assert_eq!(
+            ::std::str::from_utf8(&out).unwrap(),
+            "\
++----+-------+
+| id | name  |
++----+-------+
+|  1 | alice |
+| 10 | bob   |
++----+-------+
+"
+        );
     }
 }
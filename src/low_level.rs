@@ -0,0 +1,125 @@
+//! The building blocks tables are rendered with, for drawing a table a line at a time.
+//!
+//! This is useful for streaming rows as they arrive, where the whole table isn't known up front.
+//! The column widths have to be decided first, and every line drawn with the same widths and
+//! options. `TableWriter` does this for the common case.
+//!
+//! ```
+//! use text_tables::low_level::{self, BorderLine};
+//! use text_tables::Options;
+//!
+//! let options = Options::default();
+//! let widths = [5, 3];
+//! let mut out = String::new();
+//! let row = |cells: &[&str]| cells.iter().map(|cell| cell.to_string()).collect::<Vec<_>>();
+//! low_level::render_border_line(&mut out, &widths, &options, '-', BorderLine::Top).unwrap();
+//! low_level::render_text_line(&mut out, &widths, &options, &row(&["Alice", "42"])).unwrap();
+//! low_level::render_border_line(&mut out, &widths, &options, '-', BorderLine::Bottom).unwrap();
+//! assert_eq!(out, "+-------+-----+\n| Alice | 42  |\n+-------+-----+\n");
+//! ```
+
+use std::fmt;
+
+pub use style::BorderLine;
+use {Options, TableError};
+
+/// Get the width of each column's content, for rows rendered with `options`
+///
+/// Padding and borders are not included.
+///
+/// # Errors
+///
+/// Fails if all rows are not the same length.
+pub fn column_widths(rows: &[Vec<String>], options: &Options) -> Result<Vec<usize>, TableError> {
+    ::widths(rows, options)
+}
+
+/// Render a horizontal border line, using `horiz` for the horizontal parts
+///
+/// `line` decides which glyphs are used where the line meets the vertical borders. The normal
+/// horizontal character is `options.style.horizontal`, and the one below a header is
+/// `options.style.header_horizontal`. Nothing is written if the style or border mode has no such
+/// line.
+///
+/// # Errors
+///
+/// Fails if writing fails.
+pub fn render_border_line<W: fmt::Write>(
+    writer: &mut W,
+    widths: &[usize],
+    options: &Options,
+    horiz: char,
+    line: BorderLine,
+) -> fmt::Result {
+    ::render_border_line(writer, widths, options, horiz, line)
+}
+
+/// Render a row of cells, padded out to `widths`
+///
+/// A cell containing line breaks makes the row take up more than one line.
+///
+/// # Errors
+///
+/// Fails if writing fails.
+pub fn render_text_line<W: fmt::Write>(
+    writer: &mut W,
+    widths: &[usize],
+    options: &Options,
+    row: &[String],
+) -> fmt::Result {
+    ::render_text_line(writer, widths, options, row)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{column_widths, render_border_line, render_text_line, BorderLine};
+    use Options;
+
+    #[test]
+    fn matches_render() {
+        let options = Options {
+            header_row: true,
+            ..Options::default()
+        };
+        let rows = vec![
+            vec!["name".to_string(), "score".to_string()],
+            vec!["alice".to_string(), "9000".to_string()],
+            vec!["bob".to_string(), "two\nlines".to_string()],
+        ];
+        let widths = column_widths(&rows, &options).unwrap();
+        let style = &options.style;
+        let mut out = String::new();
+        render_border_line(
+            &mut out,
+            &widths,
+            &options,
+            style.horizontal,
+            BorderLine::Top,
+        )
+        .unwrap();
+        render_text_line(&mut out, &widths, &options, &rows[0]).unwrap();
+        let header = style.header_horizontal;
+        render_border_line(&mut out, &widths, &options, header, BorderLine::Middle).unwrap();
+        render_text_line(&mut out, &widths, &options, &rows[1]).unwrap();
+        let horiz = style.horizontal;
+        render_border_line(&mut out, &widths, &options, horiz, BorderLine::Middle).unwrap();
+        render_text_line(&mut out, &widths, &options, &rows[2]).unwrap();
+        render_border_line(&mut out, &widths, &options, horiz, BorderLine::Bottom).unwrap();
+
+        let mut rendered = Vec::new();
+        ::render_with_options(&mut rendered, &rows, &options).unwrap();
+        assert_eq!(out, String::from_utf8(rendered).unwrap());
+    }
+
+    #[test]
+    fn widths_follow_options() {
+        let rows = vec![vec!["\x1b[1mbold\x1b[0m".to_string()]];
+        assert!(column_widths(&rows, &Options::default()).unwrap()[0] > 4);
+        let options = Options {
+            strip_ansi_for_width: true,
+            ..Options::default()
+        };
+        assert_eq!(column_widths(&rows, &options).unwrap(), vec![4]);
+        assert!(column_widths(&[vec![], vec!["a".to_string()]], &options).is_err());
+    }
+}
@@ -0,0 +1,170 @@
+//! Rendering tables as GitHub-Flavored Markdown.
+
+use std::{cmp, fmt, fmt::Display, io};
+
+use {format_rows, render_text_line, widths, write_io, Alignment, Options, Style, TableError};
+
+/// The narrowest a column can be and still fit a delimiter like `:-:`.
+const MIN_WIDTH: usize = 3;
+
+/// Render the table as a GitHub-Flavored Markdown table
+///
+/// The first row is used as the header. `alignments` adds alignment hints (`:---`, `---:`,
+/// `:---:`) to the delimiter row, and aligns the text to match. Columns without an entry get a
+/// plain `---`.
+///
+/// `|` characters in cells are escaped, and line breaks are replaced with `<br>` since markdown
+/// cells can't span lines.
+///
+/// ```
+/// use text_tables::Alignment;
+///
+/// let data = [["Item", "Cost"], ["Tea", "1.50"]];
+/// let mut out = Vec::new();
+/// text_tables::render_markdown(&mut out, data, &[Alignment::Left, Alignment::Right]).unwrap();
+/// assert_eq!(::std::str::from_utf8(&out).unwrap(), "\
+/// | Item | Cost |
+/// | :--- | ---: |
+/// | Tea  | 1.50 |
+/// ");
+/// ```
+///
+/// # Errors
+///
+/// Fails if all rows are not the same length, or if writing fails.
+pub fn render_markdown<W, T, R, C>(
+    writer: &mut W,
+    data: T,
+    alignments: &[Alignment],
+) -> Result<(), TableError>
+where
+    W: io::Write,
+    T: AsRef<[R]>,
+    R: AsRef<[C]>,
+    C: Display,
+{
+    let mut rows = format_rows(data);
+    for cell in rows.iter_mut().flat_map(|row| row.iter_mut()) {
+        *cell = escape(cell);
+    }
+    write_io(writer, |writer| write_markdown(writer, &rows, alignments))
+}
+
+fn write_markdown<W: fmt::Write>(
+    writer: &mut W,
+    rows: &[Vec<String>],
+    alignments: &[Alignment],
+) -> Result<(), TableError> {
+    let options = Options {
+        style: Style::markdown(),
+        alignments: alignments.to_vec(),
+        ..Options::default()
+    };
+    let widths = widths(rows, &options)?
+        .into_iter()
+        .map(|width| cmp::max(width, MIN_WIDTH))
+        .collect::<Vec<_>>();
+    let mut rows = rows.iter();
+    if let Some(header) = rows.next() {
+        render_text_line(writer, &widths, &options, header)?;
+        render_delimiter_line(writer, &widths, &options)?;
+    }
+    for row in rows {
+        render_text_line(writer, &widths, &options, row)?;
+    }
+    Ok(())
+}
+
+/// Render the line between the header and the body, with any alignment hints.
+fn render_delimiter_line<W: fmt::Write>(
+    writer: &mut W,
+    widths: &[usize],
+    options: &Options,
+) -> fmt::Result {
+    writer.write_char('|')?;
+    for (idx, width) in widths.iter().enumerate() {
+        let (left, right) = match options.alignments.get(idx) {
+            None => ("-", "-"),
+            Some(&Alignment::Left) => (":", "-"),
+            Some(&Alignment::Right) => ("-", ":"),
+            Some(&Alignment::Center) => (":", ":"),
+        };
+        write!(writer, " {}", left)?;
+        for _ in 0..width - 2 {
+            writer.write_char('-')?;
+        }
+        write!(writer, "{} |", right)?;
+    }
+    write!(writer, "{}", options.line_ending)
+}
+
+/// Make cell content safe to put in a markdown table.
+fn escape(cell: &str) -> String {
+    cell.replace('|', "\\|").replace('\n', "<br>")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::render_markdown;
+    use Alignment;
+
+    fn markdown(data: &[Vec<&str>], alignments: &[Alignment]) -> String {
+        let mut out = Vec::new();
+        render_markdown(&mut out, data, alignments).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn render() {
+        assert_eq!(markdown(&[], &[]), "");
+        assert_eq!(
+            markdown(&[vec!["a", "header"]], &[]),
+            "\
+| a   | header |
+| --- | ------ |
+"
+        );
+        assert_eq!(
+            markdown(
+                &[vec!["name", "value"], vec!["one", "1"], vec!["two", "2"]],
+                &[]
+            ),
+            "\
+| name | value |
+| ---- | ----- |
+| one  | 1     |
+| two  | 2     |
+"
+        );
+    }
+
+    #[test]
+    fn alignment_hints() {
+        assert_eq!(
+            markdown(
+                &[
+                    vec!["left", "right", "center", "none"],
+                    vec!["a", "b", "c", "d"]
+                ],
+                &[Alignment::Left, Alignment::Right, Alignment::Center]
+            ),
+            "\
+| left | right | center | none |
+| :--- | ----: | :----: | ---- |
+| a    |     b |   c    | d    |
+"
+        );
+    }
+
+    #[test]
+    fn escaping() {
+        assert_eq!(
+            markdown(&[vec!["a|b"], vec!["c\nd"]], &[]),
+            "\
+| a\\|b   |
+| ------ |
+| c<br>d |
+"
+        );
+    }
+}
@@ -0,0 +1,356 @@
+//! Rendering options.
+
+use std::{cmp, fmt, sync::Arc};
+
+use width::{str_width, strip_ansi, OSC8};
+use Style;
+
+/// How the content of a cell is positioned within its column.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Alignment {
+    /// Pad on the right.
+    #[default]
+    Left,
+    /// Pad on the left.
+    Right,
+    /// Pad on both sides. When the padding can't be split evenly the extra space goes on the
+    /// right.
+    Center,
+}
+
+/// Which border lines are drawn.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BorderMode {
+    /// A border line above and below every row.
+    #[default]
+    Full,
+    /// Border lines only at the top and bottom of the table, and below the header.
+    Compact,
+    /// Border lines between the rows and columns, but none around the edges of the table.
+    InnerOnly,
+    /// No horizontal border lines at all, just the vertical lines between cells, so each line of
+    /// output is a row.
+    VerticalOnly,
+    /// Border lines only above and below the header. There are no lines between the other rows,
+    /// or at the bottom of the table.
+    HeaderOnly,
+}
+
+/// What to do with cells that are wider than their column's maximum width.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Overflow {
+    /// Wrap the content onto multiple lines.
+    #[default]
+    Wrap,
+    /// Cut the content short, ending it with `Options::truncation_marker`.
+    Truncate,
+}
+
+/// How wide a column can be, as set by `Options::column_policies`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ColumnWidthPolicy {
+    /// As wide as the column's content.
+    #[default]
+    Auto,
+    /// Exactly this wide.
+    Fixed(usize),
+    /// As wide as the content, but at least this wide.
+    Min(usize),
+    /// As wide as the content, but at most this wide.
+    Max(usize),
+    /// As wide as the content, but at least the first width and at most the second.
+    MinMax(usize, usize),
+}
+
+impl ColumnWidthPolicy {
+    /// Get the minimum and maximum width allowed by the policy.
+    fn limits(self) -> (usize, Option<usize>) {
+        match self {
+            ColumnWidthPolicy::Auto => (0, None),
+            ColumnWidthPolicy::Fixed(width) => (width, Some(width)),
+            ColumnWidthPolicy::Min(min) => (min, None),
+            ColumnWidthPolicy::Max(max) => (0, Some(max)),
+            ColumnWidthPolicy::MinMax(min, max) => (min, Some(max)),
+        }
+    }
+}
+
+/// The characters written at the end of each line.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LineEnding {
+    /// `\n`, as used on Unix.
+    #[default]
+    Lf,
+    /// `\r\n`, as used on Windows and in network protocols like HTTP.
+    CrLf,
+}
+
+impl LineEnding {
+    /// Get the line ending as a string.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            LineEnding::Lf => "\n",
+            LineEnding::CrLf => "\r\n",
+        }
+    }
+}
+
+/// Writes the line ending itself, so it can be used in `write!`.
+impl fmt::Display for LineEnding {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// How a single row is drawn, as chosen by `Options::row_style`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RowStyle {
+    /// Written before each cell in the row, such as an ANSI colour code.
+    pub prefix: String,
+    /// Written after each cell in the row, such as the ANSI reset code `"\x1b[0m"`.
+    pub suffix: String,
+    /// Draw a border line below the row, even if `border_mode` wouldn't.
+    pub is_separator: bool,
+}
+
+/// Chooses the style of each row, given its index. See `Options::row_style`.
+///
+/// This is shared rather than boxed, so `Options` can still be cloned.
+#[derive(Clone)]
+pub struct RowStyleFn(Arc<dyn Fn(usize) -> RowStyle + Send + Sync>);
+
+impl RowStyleFn {
+    /// Wrap a function that chooses the style of each row.
+    pub fn new<F>(f: F) -> Self
+    where
+        F: Fn(usize) -> RowStyle + Send + Sync + 'static,
+    {
+        RowStyleFn(Arc::new(f))
+    }
+}
+
+impl fmt::Debug for RowStyleFn {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("RowStyleFn")
+    }
+}
+
+/// Options controlling how a table is rendered.
+///
+/// The default options render a plain ASCII table, the same as `render`. Override just the
+/// fields you need:
+///
+/// ```
+/// use text_tables::{Alignment, Options};
+///
+/// let options = Options {
+///     alignments: vec![Alignment::Left, Alignment::Right],
+///     header_row: true,
+///     ..Options::default()
+/// };
+/// let data = [["Item", "Cost"], ["Tea", "1.50"]];
+/// let mut out = Vec::new();
+/// text_tables::render_with_options(&mut out, data, &options).unwrap();
+/// ```
+#[derive(Debug, Clone)]
+pub struct Options {
+    /// The characters used to draw borders.
+    pub style: Style,
+    /// The alignment of each column. Columns without an entry are left-aligned.
+    pub alignments: Vec<Alignment>,
+    /// The maximum width of each column. Cells wider than this are wrapped or truncated,
+    /// depending on `overflow`. Columns without an entry can be any width.
+    pub max_column_widths: Vec<Option<usize>>,
+    /// How to fit cells that are wider than `max_column_widths`.
+    pub overflow: Overflow,
+    /// Appended to truncated cells when `overflow` is `Overflow::Truncate`. Defaults to `…`.
+    pub truncation_marker: String,
+    /// The minimum width of each column. Columns without an entry can be as narrow as their
+    /// content.
+    pub min_column_widths: Vec<usize>,
+    /// How wide each column can be. This is combined with `min_column_widths` and
+    /// `max_column_widths`, and cells wider than the maximum are fitted as set by `overflow`.
+    /// Columns without an entry are `ColumnWidthPolicy::Auto`.
+    pub column_policies: Vec<ColumnWidthPolicy>,
+    /// Whether the first row is a header, separated from the rest of the table with
+    /// `style.header_horizontal`.
+    pub header_row: bool,
+    /// Draw a `style.header_horizontal` line below the first row, so it looks like a header,
+    /// without treating it as one. This has no effect when the table has a header. Defaults to
+    /// `false`.
+    pub header_separator: bool,
+    /// Whether the last row is a footer, such as a row of totals, separated from the rest of the
+    /// table with `style.header_horizontal`.
+    pub footer_row: bool,
+    /// Which horizontal border lines are drawn.
+    pub border_mode: BorderMode,
+    /// The number of spaces on each side of a cell's content. Defaults to 1.
+    pub padding: usize,
+    /// The number of blank lines written above and below the table, to set it apart from the text
+    /// around it. Defaults to 0.
+    pub outer_padding_lines: usize,
+    /// Ignore ANSI escape sequences, such as colour codes, when measuring cells. The sequences
+    /// are still written out as they are. Defaults to `false`.
+    pub strip_ansi_for_width: bool,
+    /// A heading printed above the table, centered across its full width.
+    pub title: Option<String>,
+    /// The characters written at the end of each line. Defaults to `\n`.
+    pub line_ending: LineEnding,
+    /// ANSI escape codes to colour alternate rows with, such as `"\x1b[48;5;236m"` for a grey
+    /// background. The first code is used for the first row after the header, the second for the
+    /// next, and so on. Each cell is followed by a reset code. Borders are never coloured.
+    pub zebra_stripe: Option<(String, String)>,
+    /// The index of the last column in each group of columns. Groups are separated by a doubled
+    /// vertical border, like `||`. Ignored if the style has no borders.
+    pub column_groups: Vec<usize>,
+    /// Allow rows to have different numbers of cells, padding short rows with empty cells.
+    /// Otherwise rendering fails if all rows are not the same length.
+    pub allow_ragged: bool,
+    /// The indices of columns to leave out of the table. The other per-column options still
+    /// refer to columns by their index before any are hidden.
+    pub hidden_columns: Vec<usize>,
+    /// Chooses a style for each row after the header, given its index starting from 0. This
+    /// takes the place of `zebra_stripe`, and can also add border lines below rows.
+    ///
+    /// ```
+    /// use text_tables::{Options, RowStyle, RowStyleFn};
+    ///
+    /// // highlight the third row in red.
+    /// let options = Options {
+    ///     row_style: Some(RowStyleFn::new(|idx| match idx {
+    ///         2 => RowStyle {
+    ///             prefix: "\x1b[31m".to_string(),
+    ///             suffix: "\x1b[0m".to_string(),
+    ///             ..RowStyle::default()
+    ///         },
+    ///         _ => RowStyle::default(),
+    ///     })),
+    ///     ..Options::default()
+    /// };
+    /// ```
+    pub row_style: Option<RowStyleFn>,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Options {
+            style: Style::default(),
+            alignments: Vec::new(),
+            max_column_widths: Vec::new(),
+            overflow: Overflow::default(),
+            truncation_marker: "…".to_string(),
+            min_column_widths: Vec::new(),
+            column_policies: Vec::new(),
+            header_row: false,
+            header_separator: false,
+            footer_row: false,
+            border_mode: BorderMode::default(),
+            padding: 1,
+            outer_padding_lines: 0,
+            strip_ansi_for_width: false,
+            title: None,
+            line_ending: LineEnding::default(),
+            zebra_stripe: None,
+            column_groups: Vec::new(),
+            allow_ragged: false,
+            hidden_columns: Vec::new(),
+            row_style: None,
+        }
+    }
+}
+
+impl Options {
+    /// Get the alignment for the given column.
+    pub(crate) fn alignment(&self, column: usize) -> Alignment {
+        self.alignments.get(column).cloned().unwrap_or_default()
+    }
+
+    /// Get the maximum width for the given column, if there is one.
+    pub(crate) fn max_column_width(&self, column: usize) -> Option<usize> {
+        let max = self.max_column_widths.get(column).cloned().unwrap_or(None);
+        match (max, self.policy(column).limits().1) {
+            (Some(max), Some(policy_max)) => Some(cmp::min(max, policy_max)),
+            (max, policy_max) => max.or(policy_max),
+        }
+    }
+
+    /// Whether any column has a maximum width.
+    pub(crate) fn has_max_widths(&self) -> bool {
+        self.max_column_widths.iter().any(Option::is_some)
+            || self.column_policies.iter().any(|p| p.limits().1.is_some())
+    }
+
+    /// Get the minimum width for the given column.
+    pub(crate) fn min_column_width(&self, column: usize) -> usize {
+        let min = self.min_column_widths.get(column).cloned().unwrap_or(0);
+        cmp::max(min, self.policy(column).limits().0)
+    }
+
+    /// Get the width policy for the given column.
+    fn policy(&self, column: usize) -> ColumnWidthPolicy {
+        self.column_policies
+            .get(column)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// Get the style of the given row, if `row_style` is set.
+    pub(crate) fn row_style(&self, row: usize) -> Option<RowStyle> {
+        self.row_style.as_ref().map(|f| (f.0)(row))
+    }
+
+    /// Whether the given row has a separator below it because of `row_style`.
+    pub(crate) fn is_separator(&self, row: usize) -> bool {
+        self.row_style(row).is_some_and(|style| style.is_separator)
+    }
+
+    /// Whether borders are drawn around the edges of the table.
+    pub(crate) fn outer_borders(&self) -> bool {
+        self.style.outer_borders && self.border_mode != BorderMode::InnerOnly
+    }
+
+    /// Whether a group of columns ends after the given column.
+    pub(crate) fn ends_group(&self, column: usize) -> bool {
+        self.column_groups.contains(&column)
+    }
+
+    /// Get options for the table with the hidden columns taken out, so each per-column option
+    /// refers to the column by its new index.
+    pub(crate) fn without_hidden_columns(&self) -> Options {
+        fn keep<T: Clone>(values: &[T], visible: impl Fn(&usize) -> bool) -> Vec<T> {
+            values
+                .iter()
+                .enumerate()
+                .filter(|&(idx, _)| visible(&idx))
+                .map(|(_, value)| value.clone())
+                .collect()
+        }
+        let visible = |column: &usize| !self.hidden_columns.contains(column);
+        // a group that ends at a hidden column now ends at the last visible column before it.
+        let column_groups = self
+            .column_groups
+            .iter()
+            .filter_map(|&end| (0..=end).filter(visible).count().checked_sub(1))
+            .collect();
+        Options {
+            alignments: keep(&self.alignments, visible),
+            max_column_widths: keep(&self.max_column_widths, visible),
+            min_column_widths: keep(&self.min_column_widths, visible),
+            column_policies: keep(&self.column_policies, visible),
+            column_groups,
+            hidden_columns: Vec::new(),
+            ..self.clone()
+        }
+    }
+
+    /// Get the display width of a single line of text, skipping ANSI escapes if asked to, or if
+    /// the line has a hyperlink.
+    pub(crate) fn text_width(&self, line: &str) -> usize {
+        // hyperlinks always need stripping, since their URL is never displayed.
+        if self.strip_ansi_for_width || line.contains(OSC8) {
+            str_width(&strip_ansi(line))
+        } else {
+            str_width(line)
+        }
+    }
+}
@@ -0,0 +1,105 @@
+//! Rendering tables in Emacs Org-mode format.
+
+use std::{fmt, fmt::Display, io};
+
+use {format_rows, render_text_line, widths, write_io, Options, TableError};
+
+/// Render the table as an Org-mode table
+///
+/// The first row is used as the header, with a `|---+---|` rule below it. Org tables have no
+/// outer border lines. Line breaks in cells are replaced with spaces, since Org cells can't span
+/// lines.
+///
+/// ```
+/// let data = [["Name", "Age"], ["Alice", "42"]];
+/// let mut out = Vec::new();
+/// text_tables::render_org(&mut out, data).unwrap();
+/// assert_eq!(::std::str::from_utf8(&out).unwrap(), "\
+/// | Name  | Age |
+/// |-------+-----|
+/// | Alice | 42  |
+/// ");
+/// ```
+///
+/// # Errors
+///
+/// Fails if all rows are not the same length, or if writing fails.
+pub fn render_org<W, T, R, C>(writer: &mut W, data: T) -> Result<(), TableError>
+where
+    W: io::Write,
+    T: AsRef<[R]>,
+    R: AsRef<[C]>,
+    C: Display,
+{
+    let mut rows = format_rows(data);
+    for cell in rows.iter_mut().flat_map(|row| row.iter_mut()) {
+        *cell = cell.replace('\n', " ");
+    }
+    write_io(writer, |writer| write_org(writer, &rows))
+}
+
+fn write_org<W: fmt::Write>(writer: &mut W, rows: &[Vec<String>]) -> Result<(), TableError> {
+    // the text lines are the same as the default ascii style.
+    let options = Options::default();
+    let widths = widths(rows, &options)?;
+    let mut rows = rows.iter();
+    if let Some(header) = rows.next() {
+        render_text_line(writer, &widths, &options, header)?;
+        render_org_border_line(writer, &widths, &options)?;
+    }
+    for row in rows {
+        render_text_line(writer, &widths, &options, row)?;
+    }
+    Ok(())
+}
+
+/// Render an Org-mode rule, like `|---+---|`.
+fn render_org_border_line<W: fmt::Write>(
+    writer: &mut W,
+    widths: &[usize],
+    options: &Options,
+) -> fmt::Result {
+    if widths.is_empty() {
+        return Ok(());
+    }
+    writer.write_char('|')?;
+    for (idx, width) in widths.iter().enumerate() {
+        if idx > 0 {
+            writer.write_char('+')?;
+        }
+        for _ in 0..width + 2 {
+            writer.write_char('-')?;
+        }
+    }
+    write!(writer, "|{}", options.line_ending)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::render_org;
+
+    fn org(data: &[Vec<&str>]) -> String {
+        let mut out = Vec::new();
+        render_org(&mut out, data).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn render() {
+        assert_eq!(org(&[]), "");
+        assert_eq!(org(&[vec!["a", "bb"]]), "| a | bb |\n|---+----|\n");
+        assert_eq!(
+            org(&[
+                vec!["name", "value"],
+                vec!["one", "1"],
+                vec!["two\nlines", "2"]
+            ]),
+            "\
+| name      | value |
+|-----------+-------|
+| one       | 1     |
+| two lines | 2     |
+"
+        );
+    }
+}
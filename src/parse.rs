@@ -0,0 +1,227 @@
+//! Reading a rendered table back into a `Table`.
+
+use width::char_width;
+use {ParseError, Table};
+
+impl Table {
+    /// Parse a table rendered in the default style, like the output of `render`
+    ///
+    /// A row followed by a `=` border line, like the one `render_with_header` draws, becomes the
+    /// header. Each row must be enclosed by border lines, so tables rendered with
+    /// `BorderMode::Compact` or other styles can't be parsed.
+    ///
+    /// Cells are read back as left-aligned text. Spaces at the end of each line of a cell, and
+    /// blank lines at the end of a cell, can't be told apart from padding and are dropped.
+    ///
+    /// ```
+    /// use text_tables::Table;
+    ///
+    /// let mut table = Table::new();
+    /// table.add_header(["name", "age"]).add_row(["Alice", "42"]);
+    /// assert_eq!(Table::parse(&table.to_string()), Ok(table));
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Fails if a line is not a border line or a row of cells that lines up with the borders.
+    pub fn parse(text: &str) -> Result<Table, ParseError> {
+        let mut table = Table::new();
+        let mut lines = text.lines().enumerate().map(|(idx, line)| (idx + 1, line));
+        let widths = match lines.next() {
+            Some((number, line)) => match parse_border_line(line) {
+                Some((widths, false)) => widths,
+                _ => return Err(ParseError::new(number, "is not a top border line")),
+            },
+            None => return Ok(table),
+        };
+        // the lines of each cell in the current row.
+        let mut row: Option<Vec<Vec<String>>> = None;
+        let mut first_row = true;
+        let mut last = 1;
+        for (number, line) in lines {
+            last = number;
+            if line.starts_with('+') {
+                let header = match parse_border_line(line) {
+                    Some((ref border, header)) if *border == widths => header,
+                    _ => return Err(ParseError::new(number, "does not match the top border")),
+                };
+                let cells = match row.take() {
+                    Some(cells) => cells.into_iter().map(join_cell_lines),
+                    None => return Err(ParseError::new(number, "is not below a row")),
+                };
+                if header && first_row {
+                    table.add_header(cells);
+                } else {
+                    table.add_row(cells);
+                }
+                first_row = false;
+            } else {
+                let cells = match parse_text_line(line, &widths) {
+                    Some(cells) => cells,
+                    None => {
+                        return Err(ParseError::new(number, "does not line up with the borders"))
+                    }
+                };
+                let row = row.get_or_insert_with(|| vec![Vec::new(); widths.len()]);
+                for (lines, cell) in row.iter_mut().zip(cells) {
+                    lines.push(cell);
+                }
+            }
+        }
+        if row.is_some() {
+            return Err(ParseError::new(last, "is not followed by a border line"));
+        }
+        Ok(table)
+    }
+}
+
+/// Parse a line like `+---+---+`, getting the width of each column including its padding, and
+/// whether it is drawn with `=`.
+fn parse_border_line(line: &str) -> Option<(Vec<usize>, bool)> {
+    if line.len() < 2 || !line.starts_with('+') || !line.ends_with('+') {
+        return None;
+    }
+    let horiz = line[1..].chars().next()?;
+    if horiz != '-' && horiz != '=' {
+        return None;
+    }
+    let segments = line[1..line.len() - 1].split('+');
+    let mut widths = Vec::new();
+    for segment in segments {
+        if segment.is_empty() || segment.chars().any(|c| c != horiz) {
+            return None;
+        }
+        widths.push(segment.len());
+    }
+    Some((widths, horiz == '='))
+}
+
+/// Split a line like `| a | b |` into its cells, given the width of each column including its
+/// padding.
+fn parse_text_line(line: &str, widths: &[usize]) -> Option<Vec<String>> {
+    let mut chars = line.chars();
+    if chars.next() != Some('|') {
+        return None;
+    }
+    let mut cells = Vec::new();
+    for &width in widths {
+        let mut cell = String::new();
+        let mut cell_width = 0;
+        while cell_width < width {
+            let c = chars.next()?;
+            cell_width += char_width(c);
+            cell.push(c);
+        }
+        if cell_width != width || chars.next() != Some('|') || !cell.starts_with(' ') {
+            return None;
+        }
+        cells.push(cell[1..].trim_end().to_string());
+    }
+    match chars.next() {
+        Some(_) => None,
+        None => Some(cells),
+    }
+}
+
+/// Join the lines of a cell back together, dropping the blank lines that pad it to the height
+/// of its row.
+fn join_cell_lines(lines: Vec<String>) -> String {
+    lines.join("\n").trim_end_matches('\n').to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_border_line, parse_text_line};
+    use Table;
+
+    #[test]
+    fn border_line() {
+        assert_eq!(parse_border_line("+---+-----+"), Some((vec![3, 5], false)));
+        assert_eq!(parse_border_line("+===+"), Some((vec![3], true)));
+        assert_eq!(parse_border_line("+---+===+"), None);
+        assert_eq!(parse_border_line("+--++"), None);
+        assert_eq!(parse_border_line("| a |"), None);
+        assert_eq!(parse_border_line("+"), None);
+    }
+
+    #[test]
+    fn text_line() {
+        let cells = |cells: &[&str]| Some(cells.iter().map(|c| c.to_string()).collect());
+        assert_eq!(parse_text_line("| a | bc  |", &[3, 5]), cells(&["a", "bc"]));
+        assert_eq!(
+            parse_text_line("| a|b | c |", &[5, 3]),
+            cells(&["a|b", "c"])
+        );
+        assert_eq!(parse_text_line("|  x |", &[4]), cells(&[" x"]));
+        assert_eq!(parse_text_line("| a | b", &[3, 3]), None);
+        assert_eq!(parse_text_line("| a | b |", &[3]), None);
+        assert_eq!(parse_text_line("|ab |", &[3]), None);
+    }
+
+    #[test]
+    fn errors() {
+        let line = |text: &str| Table::parse(text).unwrap_err().line();
+        assert_eq!(line("| a |\n"), 1);
+        assert_eq!(line("+---+\n| a |\n"), 2);
+        assert_eq!(line("+---+\n| a |\n+----+\n"), 3);
+        assert_eq!(line("+---+\n+---+\n"), 2);
+        assert_eq!(line("+---+\n| ab |\n+---+\n"), 2);
+        assert_eq!(Table::parse(""), Ok(Table::new()));
+    }
+
+    /// A small pseudo-random number generator, so the round trip can be tried on lots of tables
+    /// without any dependencies.
+    struct Lcg(u64);
+
+    impl Lcg {
+        fn below(&mut self, bound: usize) -> usize {
+            self.0 = self
+                .0
+                .wrapping_mul(6_364_136_223_846_793_005)
+                .wrapping_add(1_442_695_040_888_963_407);
+            (self.0 >> 33) as usize % bound
+        }
+
+        /// Make a cell that can be read back exactly: not empty, and without trailing spaces
+        /// or line breaks.
+        fn cell(&mut self) -> String {
+            const CHARS: &[&str] = &["a", "Z", "7", " ", "\n", "|", "+", "-", "=", "二", "é"];
+            let mut cell = String::new();
+            for _ in 0..self.below(8) {
+                cell.push_str(CHARS[self.below(CHARS.len())]);
+            }
+            let lines = cell.lines().map(str::trim_end).collect::<Vec<_>>();
+            lines.join("\n") + "x"
+        }
+    }
+
+    #[test]
+    fn round_trip() {
+        let mut rng = Lcg(42);
+        for _ in 0..200 {
+            let columns = rng.below(4) + 1;
+            let data = (0..rng.below(5) + 1)
+                .map(|_| (0..columns).map(|_| rng.cell()).collect::<Vec<_>>())
+                .collect::<Vec<_>>();
+            let table = data.iter().collect::<Table>();
+            assert_eq!(
+                Table::parse(&::render_to_string(&data)),
+                Ok(table.clone()),
+                "{:?}",
+                data
+            );
+
+            let mut with_header = Table::new();
+            with_header.add_header(&data[0]);
+            for row in &data[1..] {
+                with_header.add_row(row);
+            }
+            assert_eq!(
+                Table::parse(&with_header.to_string()),
+                Ok(with_header),
+                "{:?}",
+                data
+            );
+        }
+    }
+}
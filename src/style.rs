@@ -0,0 +1,358 @@
+//! Border styles.
+
+/// The characters used to draw the borders of a table.
+///
+/// Each glyph is a `char`, so multi-byte characters such as the box-drawing set can be used
+/// freely: widths are always counted in characters, never in bytes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Style {
+    /// Drawn where inner horizontal and vertical borders cross.
+    pub corner: char,
+    /// Used for horizontal borders.
+    pub horizontal: char,
+    /// Used for vertical borders.
+    pub vertical: char,
+    /// The top left corner of the table.
+    pub top_left: char,
+    /// The top right corner of the table.
+    pub top_right: char,
+    /// The bottom left corner of the table.
+    pub bottom_left: char,
+    /// The bottom right corner of the table.
+    pub bottom_right: char,
+    /// Drawn where a horizontal border meets the left edge.
+    pub left_junction: char,
+    /// Drawn where a horizontal border meets the right edge.
+    pub right_junction: char,
+    /// Drawn where a vertical border meets the top edge.
+    pub top_junction: char,
+    /// Drawn where a vertical border meets the bottom edge.
+    pub bottom_junction: char,
+    /// Used for the horizontal border below a header row.
+    pub header_horizontal: char,
+    /// Whether to draw borders at all. When this is false none of the glyphs above are used:
+    /// cells are not padded, and columns are separated by `column_gap` spaces instead.
+    pub borders: bool,
+    /// Whether to draw the outside edges of the table. When this is false, only the borders
+    /// between cells are drawn.
+    pub outer_borders: bool,
+    /// The number of spaces between columns when `borders` is false.
+    pub column_gap: usize,
+}
+
+impl Style {
+    /// The default style, using `+`, `-`, `|` and `=`.
+    ///
+    /// ```text
+    /// +---+---+
+    /// | a | b |
+    /// +---+---+
+    /// ```
+    pub fn ascii() -> Self {
+        Style {
+            corner: '+',
+            top_left: '+',
+            top_right: '+',
+            bottom_left: '+',
+            bottom_right: '+',
+            left_junction: '+',
+            right_junction: '+',
+            top_junction: '+',
+            bottom_junction: '+',
+            horizontal: '-',
+            vertical: '|',
+            header_horizontal: '=',
+            borders: true,
+            outer_borders: true,
+            column_gap: 2,
+        }
+    }
+
+    /// Unicode box-drawing lines, with `┼` at every junction.
+    ///
+    /// See `BoxStyle` for box-drawing styles with proper corners.
+    ///
+    /// ```text
+    /// ┼───┼───┼
+    /// │ a │ b │
+    /// ┼───┼───┼
+    /// ```
+    pub fn unicode() -> Self {
+        Style {
+            corner: '┼',
+            top_left: '┼',
+            top_right: '┼',
+            bottom_left: '┼',
+            bottom_right: '┼',
+            left_junction: '┼',
+            right_junction: '┼',
+            top_junction: '┼',
+            bottom_junction: '┼',
+            horizontal: '─',
+            vertical: '│',
+            header_horizontal: '═',
+            borders: true,
+            outer_borders: true,
+            column_gap: 2,
+        }
+    }
+
+    /// The characters used by markdown tables, with `|` in place of corners.
+    ///
+    /// ```text
+    /// |---|---|
+    /// | a | b |
+    /// |---|---|
+    /// ```
+    pub fn markdown() -> Self {
+        Style {
+            corner: '|',
+            top_left: '|',
+            top_right: '|',
+            bottom_left: '|',
+            bottom_right: '|',
+            left_junction: '|',
+            right_junction: '|',
+            top_junction: '|',
+            bottom_junction: '|',
+            horizontal: '-',
+            vertical: '|',
+            header_horizontal: '-',
+            borders: true,
+            outer_borders: true,
+            column_gap: 2,
+        }
+    }
+
+    /// The style of the MySQL command line client, which is `ascii` with `-` below the header.
+    ///
+    /// ```text
+    /// +---+---+
+    /// | a | b |
+    /// +---+---+
+    /// | c | d |
+    /// +---+---+
+    /// ```
+    ///
+    /// `render_mysql` uses this with the other settings needed to match MySQL's output.
+    pub fn mysql() -> Self {
+        Style {
+            header_horizontal: '-',
+            ..Style::ascii()
+        }
+    }
+
+    /// The style of PostgreSQL's `psql`, with borders between cells but not around the edges.
+    ///
+    /// ```text
+    ///  a | b
+    /// ---+---
+    ///  c | d
+    /// ```
+    ///
+    /// `render_psql` uses this with the other settings needed to match `psql`'s output.
+    pub fn psql() -> Self {
+        Style {
+            header_horizontal: '-',
+            outer_borders: false,
+            ..Style::ascii()
+        }
+    }
+
+    /// No borders, just columns of text separated by `column_gap` spaces, like the output of
+    /// `ls -l` or `column -t`. The usual gap is 2.
+    ///
+    /// ```text
+    /// a    bb
+    /// ccc  d
+    /// ```
+    pub fn plain(column_gap: usize) -> Self {
+        Style {
+            borders: false,
+            column_gap,
+            ..Style::ascii()
+        }
+    }
+}
+
+impl Default for Style {
+    fn default() -> Self {
+        Style::ascii()
+    }
+}
+
+/// Sets of Unicode box-drawing characters, which can be turned into a `Style`.
+///
+/// ```
+/// use text_tables::{BoxStyle, Options, Style};
+///
+/// let options = Options {
+///     style: Style::from(BoxStyle::Rounded),
+///     ..Options::default()
+/// };
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BoxStyle {
+    /// Thin lines.
+    ///
+    /// ```text
+    /// ┌───┬───┐
+    /// │ a │ b │
+    /// ├───┼───┤
+    /// │ c │ d │
+    /// └───┴───┘
+    /// ```
+    Unicode,
+    /// Heavy lines.
+    ///
+    /// ```text
+    /// ┏━━━┳━━━┓
+    /// ┃ a ┃ b ┃
+    /// ┣━━━╋━━━┫
+    /// ┃ c ┃ d ┃
+    /// ┗━━━┻━━━┛
+    /// ```
+    UnicodeBold,
+    /// Double lines.
+    ///
+    /// ```text
+    /// ╔═══╦═══╗
+    /// ║ a ║ b ║
+    /// ╠═══╬═══╣
+    /// ║ c ║ d ║
+    /// ╚═══╩═══╝
+    /// ```
+    UnicodeDouble,
+    /// Thin lines with rounded corners.
+    ///
+    /// ```text
+    /// ╭───┬───╮
+    /// │ a │ b │
+    /// ├───┼───┤
+    /// │ c │ d │
+    /// ╰───┴───╯
+    /// ```
+    Rounded,
+}
+
+impl From<BoxStyle> for Style {
+    fn from(box_style: BoxStyle) -> Self {
+        // horizontal, vertical, cross, corners (clockwise from top left), then junctions (left,
+        // right, top, bottom). The line below the header uses the same junctions as the other
+        // lines, so it is drawn with the same horizontal to match them.
+        let glyphs = match box_style {
+            BoxStyle::Unicode => "─│┼┌┐┘└├┤┬┴",
+            BoxStyle::UnicodeBold => "━┃╋┏┓┛┗┣┫┳┻",
+            BoxStyle::UnicodeDouble => "═║╬╔╗╝╚╠╣╦╩",
+            BoxStyle::Rounded => "─│┼╭╮╯╰├┤┬┴",
+        };
+        let glyphs = glyphs.chars().collect::<Vec<_>>();
+        Style {
+            horizontal: glyphs[0],
+            vertical: glyphs[1],
+            corner: glyphs[2],
+            top_left: glyphs[3],
+            top_right: glyphs[4],
+            bottom_right: glyphs[5],
+            bottom_left: glyphs[6],
+            left_junction: glyphs[7],
+            right_junction: glyphs[8],
+            top_junction: glyphs[9],
+            bottom_junction: glyphs[10],
+            header_horizontal: glyphs[0],
+            borders: true,
+            outer_borders: true,
+            column_gap: 2,
+        }
+    }
+}
+
+/// Which horizontal border is being drawn, which decides the junction glyphs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BorderLine {
+    /// The line along the top of the table.
+    Top,
+    /// A line between two rows.
+    Middle,
+    /// The line along the bottom of the table.
+    Bottom,
+}
+
+impl Style {
+    /// Get the glyphs for the left edge, inner junctions and right edge of a border line.
+    pub(crate) fn junctions(&self, line: BorderLine) -> (char, char, char) {
+        match line {
+            BorderLine::Top => (self.top_left, self.top_junction, self.top_right),
+            BorderLine::Middle => (self.left_junction, self.corner, self.right_junction),
+            BorderLine::Bottom => (self.bottom_left, self.bottom_junction, self.bottom_right),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{BoxStyle, Style};
+
+    fn render(box_style: BoxStyle) -> String {
+        let options = ::Options {
+            style: Style::from(box_style),
+            ..::Options::default()
+        };
+        let mut out = Vec::new();
+        ::render_with_options(&mut out, [["a", "b"], ["c", "d"]], &options).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn box_styles() {
+        let tests = vec![
+            (
+                BoxStyle::Unicode,
+                "┌───┬───┐\n│ a │ b │\n├───┼───┤\n│ c │ d │\n└───┴───┘\n",
+            ),
+            (
+                BoxStyle::UnicodeBold,
+                "┏━━━┳━━━┓\n┃ a ┃ b ┃\n┣━━━╋━━━┫\n┃ c ┃ d ┃\n┗━━━┻━━━┛\n",
+            ),
+            (
+                BoxStyle::UnicodeDouble,
+                "╔═══╦═══╗\n║ a ║ b ║\n╠═══╬═══╣\n║ c ║ d ║\n╚═══╩═══╝\n",
+            ),
+            (
+                BoxStyle::Rounded,
+                "╭───┬───╮\n│ a │ b │\n├───┼───┤\n│ c │ d │\n╰───┴───╯\n",
+            ),
+        ];
+        for (box_style, expected) in tests {
+            assert_eq!(render(box_style), expected, "{:?}", box_style);
+        }
+    }
+
+    #[test]
+    fn box_style_headers() {
+        let render = |box_style| {
+            let options = ::Options {
+                style: Style::from(box_style),
+                header_row: true,
+                ..::Options::default()
+            };
+            let mut out = Vec::new();
+            ::render_with_options(&mut out, [["a"], ["b"]], &options).unwrap();
+            String::from_utf8(out).unwrap()
+        };
+        let tests = vec![
+            (BoxStyle::Unicode, "├───┤"),
+            (BoxStyle::UnicodeBold, "┣━━━┫"),
+            (BoxStyle::UnicodeDouble, "╠═══╣"),
+            (BoxStyle::Rounded, "├───┤"),
+        ];
+        for (box_style, expected) in tests {
+            assert_eq!(
+                render(box_style).lines().nth(2),
+                Some(expected),
+                "{:?}",
+                box_style
+            );
+        }
+    }
+}
@@ -0,0 +1,264 @@
+//! Rendering a table with a row of statistics about each column.
+
+use std::{fmt::Display, io};
+
+use {format_rows, Options, Table, TableError};
+
+/// A statistic to show below a column, for use with `render_summary`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ColumnStat {
+    /// Leave the column's summary empty.
+    #[default]
+    None,
+    /// The total of the numbers in the column.
+    Sum,
+    /// How many cells in the column are numbers.
+    Count,
+    /// The average of the numbers in the column.
+    Mean,
+    /// The largest number in the column.
+    Max,
+    /// The smallest number in the column.
+    Min,
+}
+
+impl ColumnStat {
+    /// Work out the statistic for the numbers in a column, if there are any.
+    fn compute(self, numbers: &[f64]) -> String {
+        let sum = || numbers.iter().sum::<f64>();
+        let value = match self {
+            ColumnStat::None => return String::new(),
+            ColumnStat::Count => return numbers.len().to_string(),
+            _ if numbers.is_empty() => return String::new(),
+            ColumnStat::Sum => sum(),
+            ColumnStat::Mean => sum() / numbers.len() as f64,
+            ColumnStat::Max => numbers.iter().cloned().fold(f64::NEG_INFINITY, f64::max),
+            ColumnStat::Min => numbers.iter().cloned().fold(f64::INFINITY, f64::min),
+        };
+        value.to_string()
+    }
+}
+
+/// Render the table with a row of statistics at the bottom, below a `=` separator
+///
+/// `stats` gives the statistic for each column, and columns without an entry are left empty.
+/// Cells are read as numbers with `str::parse::<f64>`, and cells that aren't numbers are
+/// skipped. A statistic of a column with no numbers is left empty, apart from `Count`, which is
+/// 0.
+///
+/// ```
+/// use text_tables::ColumnStat;
+///
+/// let data = [["tea", "1.5"], ["cake", "2"]];
+/// let mut out = Vec::new();
+/// text_tables::render_summary(&mut out, data, &[ColumnStat::Count, ColumnStat::Sum]).unwrap();
+/// assert_eq!(::std::str::from_utf8(&out).unwrap(), "\
+/// +------+-----+
+/// | tea  | 1.5 |
+/// +------+-----+
+/// | cake | 2   |
+/// +======+=====+
+/// | 0    | 3.5 |
+/// +------+-----+
+/// ");
+/// ```
+///
+/// # Errors
+///
+/// Fails if all rows are not the same length, or if writing fails.
+pub fn render_summary<W, T, R, C>(
+    writer: &mut W,
+    data: T,
+    stats: &[ColumnStat],
+) -> Result<(), TableError>
+where
+    W: io::Write,
+    T: AsRef<[R]>,
+    R: AsRef<[C]>,
+    C: Display,
+{
+    let mut rows = format_rows(data);
+    let columns = rows.first().map_or(0, Vec::len);
+    let summary = (0..columns)
+        .map(|col| {
+            let numbers = rows
+                .iter()
+                .filter_map(|row| row.get(col))
+                .filter_map(|cell| cell.trim().parse::<f64>().ok())
+                .collect::<Vec<_>>();
+            let stat = stats.get(col).cloned().unwrap_or_default();
+            stat.compute(&numbers)
+        })
+        .collect();
+    rows.push(summary);
+    let options = Options {
+        footer_row: true,
+        ..Options::default()
+    };
+    rows.into_iter()
+        .collect::<Table>()
+        .render_with_options(writer, &options)
+}
+
+/// Render the table with a row of column totals at the bottom, below a `=` separator
+///
+/// The first row is used as the header. A column is totalled only if every cell below the header
+/// is a number, read with `str::parse::<f64>`, otherwise its total is left empty. Use
+/// `render_summary` for other statistics, or to total the numbers in mixed columns.
+///
+/// ```
+/// let data = [["item", "price"], ["tea", "1.5"], ["cake", "2"]];
+/// let mut out = Vec::new();
+/// text_tables::render_with_column_totals(&mut out, data).unwrap();
+/// assert_eq!(::std::str::from_utf8(&out).unwrap(), "\
+/// +------+-------+
+/// | item | price |
+/// +======+=======+
+/// | tea  | 1.5   |
+/// +------+-------+
+/// | cake | 2     |
+/// +======+=======+
+/// |      | 3.5   |
+/// +------+-------+
+/// ");
+/// ```
+///
+/// # Errors
+///
+/// Fails if all rows are not the same length, or if writing fails.
+pub fn render_with_column_totals<W, T, R, C>(writer: &mut W, data: T) -> Result<(), TableError>
+where
+    W: io::Write,
+    T: AsRef<[R]>,
+    R: AsRef<[C]>,
+    C: Display,
+{
+    let mut rows = format_rows(data);
+    let columns = rows.first().map_or(0, Vec::len);
+    let totals = (0..columns)
+        .map(|col| {
+            let numbers = rows
+                .iter()
+                .skip(1)
+                .map(|row| {
+                    row.get(col)
+                        .and_then(|cell| cell.trim().parse::<f64>().ok())
+                })
+                .collect::<Option<Vec<_>>>();
+            match numbers {
+                Some(numbers) if !numbers.is_empty() => ColumnStat::Sum.compute(&numbers),
+                _ => String::new(),
+            }
+        })
+        .collect();
+    rows.push(totals);
+    let options = Options {
+        header_row: true,
+        footer_row: true,
+        ..Options::default()
+    };
+    rows.into_iter()
+        .collect::<Table>()
+        .render_with_options(writer, &options)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{render_summary, render_with_column_totals, ColumnStat};
+
+    fn summary(data: &[Vec<&str>], stats: &[ColumnStat]) -> String {
+        let mut out = Vec::new();
+        render_summary(&mut out, data, stats).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn stats() {
+        let data = vec![
+            vec!["a", "1", "10"],
+            vec!["b", "n/a", "-2"],
+            vec!["c", "4", "3.5"],
+        ];
+        assert_eq!(
+            summary(
+                &data,
+                &[ColumnStat::Count, ColumnStat::Mean, ColumnStat::Max]
+            ),
+            "\
++---+-----+-----+
+| a | 1   | 10  |
++---+-----+-----+
+| b | n/a | -2  |
++---+-----+-----+
+| c | 4   | 3.5 |
++===+=====+=====+
+| 0 | 2.5 | 10  |
++---+-----+-----+
+"
+        );
+        assert_eq!(
+            summary(&data, &[ColumnStat::Sum, ColumnStat::Sum, ColumnStat::Min]),
+            "\
++---+-----+-----+
+| a | 1   | 10  |
++---+-----+-----+
+| b | n/a | -2  |
++---+-----+-----+
+| c | 4   | 3.5 |
++===+=====+=====+
+|   | 5   | -2  |
++---+-----+-----+
+"
+        );
+        assert_eq!(
+            summary(&[vec!["x", "1"]], &[]),
+            "+---+---+\n| x | 1 |\n+===+===+\n|   |   |\n+---+---+\n"
+        );
+        assert_eq!(summary(&[], &[ColumnStat::Sum]), "");
+    }
+
+    fn totals(data: &[Vec<&str>]) -> String {
+        let mut out = Vec::new();
+        render_with_column_totals(&mut out, data).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn column_totals() {
+        assert_eq!(
+            totals(&[vec!["a", "b"], vec!["1", "2.5"], vec!["-3", "4"]]),
+            "\
++----+-----+
+| a  | b   |
++====+=====+
+| 1  | 2.5 |
++----+-----+
+| -3 | 4   |
++====+=====+
+| -2 | 6.5 |
++----+-----+
+"
+        );
+        // the names and the formatted number stop their columns being totalled.
+        assert_eq!(
+            totals(&[
+                vec!["name", "count", "price"],
+                vec!["tea", "3", "1,000"],
+                vec!["cake", "1", "2"],
+            ]),
+            "\
++------+-------+-------+
+| name | count | price |
++======+=======+=======+
+| tea  | 3     | 1,000 |
++------+-------+-------+
+| cake | 1     | 2     |
++======+=======+=======+
+|      | 4     |       |
++------+-------+-------+
+"
+        );
+        assert_eq!(totals(&[vec!["x"]]), "+---+\n| x |\n+===+\n|   |\n+---+\n");
+        assert_eq!(totals(&[]), "");
+    }
+}
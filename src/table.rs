@@ -0,0 +1,2507 @@
+//! A table that can be built up a row at a time.
+
+use std::{
+    any::Any,
+    borrow::Cow,
+    cmp::{self, Ordering},
+    collections::HashMap,
+    fmt::{self, Display},
+    io,
+    iter::FromIterator,
+    mem,
+    ops::Range,
+};
+
+use column::{display_any, ColumnFormat};
+use style::BorderLine;
+use wrap::{truncate, wrap};
+use {
+    cell_width, check_row_lengths, estimate_buffer_size, render_border_line,
+    render_coloured_text_line, render_text_line, render_title_line, row_height, table_width,
+    widths, write_io, Alignment, BorderMode, ColumnDef, Options, Overflow, TableError, ANSI_RESET,
+};
+
+/// A table that can be built incrementally before rendering.
+///
+/// Each cell is formatted using its `Display` impl when it is added, so the table owns all its
+/// data. Tables compare equal when they have the same header and cells.
+///
+/// # Example
+///
+/// ```
+/// let mut table = text_tables::Table::new();
+/// table.add_header(["Name", "Age"]).add_row(["Alice", "42"]);
+/// let mut out = Vec::new();
+/// table.render(&mut out).unwrap();
+/// println!("{}", ::std::str::from_utf8(&out).unwrap());
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Table {
+    header: Option<Vec<String>>,
+    rows: Vec<Vec<String>>,
+    /// Columns added with `add_column_def`.
+    columns: Vec<ColumnFormat>,
+    /// Whether the first row is treated as the header by row operations, when there is no
+    /// header. See `freeze_header`.
+    header_frozen: bool,
+    /// The width of the widest cell `set_cell` has replaced in each column, so that columns don't
+    /// get narrower. See `shrink_columns`.
+    kept_widths: Vec<KeptWidth>,
+}
+
+/// The width of a cell that has been replaced, measured both ways `Options` can measure it, so
+/// the column is kept as wide as it would have been with the options it is rendered with.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+struct KeptWidth {
+    /// The width with ANSI escapes counted, apart from hyperlinks.
+    plain: usize,
+    /// The width with ANSI escapes stripped, for `Options::strip_ansi_for_width`.
+    stripped: usize,
+}
+
+impl KeptWidth {
+    /// Measure `cell`.
+    fn of(cell: &str) -> Self {
+        let stripped = Options {
+            strip_ansi_for_width: true,
+            ..Options::default()
+        };
+        KeptWidth {
+            plain: cell_width(cell, &Options::default()),
+            stripped: cell_width(cell, &stripped),
+        }
+    }
+
+    /// Get the widest of the two widths.
+    fn max(self, other: KeptWidth) -> Self {
+        KeptWidth {
+            plain: cmp::max(self.plain, other.plain),
+            stripped: cmp::max(self.stripped, other.stripped),
+        }
+    }
+
+    /// Get the width as it is measured with or without `Options::strip_ansi_for_width`.
+    fn get(self, strip_ansi: bool) -> usize {
+        if strip_ansi {
+            self.stripped
+        } else {
+            self.plain
+        }
+    }
+}
+
+impl Table {
+    /// Create an empty table.
+    pub fn new() -> Self {
+        Table::default()
+    }
+
+    /// Add a row to the bottom of the table.
+    pub fn add_row<R, C>(&mut self, row: R) -> &mut Self
+    where
+        R: IntoIterator<Item = C>,
+        C: Display,
+    {
+        self.rows.push(format_row(row));
+        self
+    }
+
+    /// Add a row to the bottom of the table, with its first cell indented by two spaces for each
+    /// level of `depth`.
+    ///
+    /// This is useful for showing a tree, such as a directory listing. Each line of the first
+    /// cell is indented.
+    ///
+    /// ```
+    /// let mut table = text_tables::Table::new();
+    /// table
+    ///     .add_row_indented(["src", "dir"], 0)
+    ///     .add_row_indented(["lib.rs", "file"], 1);
+    /// assert_eq!(table.to_string(), "\
+    /// +----------+------+
+    /// | src      | dir  |
+    /// +----------+------+
+    /// |   lib.rs | file |
+    /// +----------+------+
+    /// ");
+    /// ```
+    pub fn add_row_indented<R, C>(&mut self, row: R, depth: usize) -> &mut Self
+    where
+        R: IntoIterator<Item = C>,
+        C: Display,
+    {
+        let mut row = format_row(row);
+        if let Some(first) = row.first_mut() {
+            let indent = "  ".repeat(depth);
+            *first = first
+                .split('\n')
+                .map(|line| format!("{}{}", indent, line))
+                .collect::<Vec<_>>()
+                .join("\n");
+        }
+        self.rows.push(row);
+        self
+    }
+
+    /// Set the header row of the table.
+    ///
+    /// The header is always rendered first, with a `=` separator below it, no matter when it is
+    /// set. Setting the header again replaces the previous one. The header is frozen, so row
+    /// operations like sorting leave it alone.
+    pub fn add_header<R, C>(&mut self, row: R) -> &mut Self
+    where
+        R: IntoIterator<Item = C>,
+        C: Display,
+    {
+        self.header = Some(format_row(row));
+        self
+    }
+
+    /// Treat the first row as a frozen header, for tables that are rendered with
+    /// `Options::header_row` rather than given a header with `add_header`.
+    ///
+    /// Row operations then treat the first row as they do a header added with `add_header`:
+    /// sorting and filtering leave it where it is, and it is not counted by row indices, so row
+    /// `0` is the one below it.
+    ///
+    /// ```
+    /// use text_tables::Table;
+    ///
+    /// let mut table = ["name", "b", "a"].iter().map(|cell| vec![cell]).collect::<Table>();
+    /// table.freeze_header().sort_by_column(0, true);
+    /// assert_eq!(table.row_count(), 2);
+    /// assert_eq!(table.get_cell(0, 0), Some("a"));
+    /// assert_eq!(table.get_cell(1, 0), Some("b"));
+    /// ```
+    pub fn freeze_header(&mut self) -> &mut Self {
+        self.header_frozen = true;
+        self
+    }
+
+    /// Whether row operations leave the header in place, which is always the case for a header
+    /// added with `add_header`.
+    pub fn is_header_frozen(&self) -> bool {
+        self.header.is_some() || self.header_frozen
+    }
+
+    /// Get the number of rows at the top that row operations must leave alone, which is the first
+    /// row if it has been frozen as the header.
+    fn frozen_rows(&self) -> usize {
+        if self.header_frozen && self.header.is_none() {
+            cmp::min(1, self.rows.len())
+        } else {
+            0
+        }
+    }
+
+    /// Get the header, or the first row if it has been frozen as the header.
+    fn header_row(&self) -> Option<&Vec<String>> {
+        self.header
+            .as_ref()
+            .or_else(|| self.rows[..self.frozen_rows()].first())
+    }
+
+    /// Get the rows that row operations work on, which leaves out a frozen first row.
+    fn data_rows(&self) -> &[Vec<String>] {
+        &self.rows[self.frozen_rows()..]
+    }
+
+    /// Change the header of column `col` to `name`.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the table has no header, or the header has no such column.
+    pub fn rename_header<S: Into<String>>(
+        &mut self,
+        col: usize,
+        name: S,
+    ) -> Result<(), TableError> {
+        let header = match self.header {
+            Some(ref mut header) => header,
+            None => return Err(TableError::NoHeaderRow),
+        };
+        let len = header.len();
+        match header.get_mut(col) {
+            Some(cell) => *cell = name.into(),
+            None => return Err(TableError::ColumnIndexOutOfRange { index: col, len }),
+        }
+        Ok(())
+    }
+
+    /// Add a column described by `def`, adding its header to the table's header.
+    ///
+    /// Cells for these columns are added with `add_row_any`, and the column's alignment is used
+    /// unless the render options give alignments of their own.
+    pub fn add_column_def(&mut self, def: ColumnDef) -> &mut Self {
+        self.header
+            .get_or_insert_with(Vec::new)
+            .push(def.header.clone());
+        self.columns.push(def.into());
+        self
+    }
+
+    /// Add a row of values of any type to the bottom of the table.
+    ///
+    /// Each value is turned into text by the formatter of the matching `ColumnDef`. Values
+    /// without a formatter, including any past the defined columns, are displayed if they are a
+    /// string, number, `bool` or `char`, and left empty otherwise.
+    pub fn add_row_any(&mut self, row: Vec<Box<dyn Any>>) -> &mut Self {
+        let row = row
+            .iter()
+            .enumerate()
+            .map(|(idx, value)| match self.columns.get(idx) {
+                Some(column) => column.format(&**value),
+                None => display_any(&**value),
+            })
+            .collect();
+        self.rows.push(row);
+        self
+    }
+
+    /// Add a column on the right, with the given header and a value for each row.
+    ///
+    /// A table without rows gets a row for each value. If the table has no header yet, the other
+    /// columns' headers are left empty.
+    ///
+    /// ```
+    /// let mut table = text_tables::Table::new();
+    /// table.add_column("name", ["Alice", "Bob"]).unwrap();
+    /// table.add_column("age", [42, 17]).unwrap();
+    /// assert_eq!(table.to_string(), "\
+    /// +-------+-----+
+    /// | name  | age |
+    /// +=======+=====+
+    /// | Alice | 42  |
+    /// +-------+-----+
+    /// | Bob   | 17  |
+    /// +-------+-----+
+    /// ");
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Fails if the table has rows, and there isn't one value for each of them.
+    pub fn add_column<S, I, C>(&mut self, header: S, values: I) -> Result<(), TableError>
+    where
+        S: Into<String>,
+        I: IntoIterator<Item = C>,
+        C: Display,
+    {
+        let values = format_row(values);
+        let (expected, got) = (self.row_count(), values.len());
+        if expected > 0 && expected != got {
+            return Err(TableError::IncompatibleRowCounts { expected, got });
+        }
+        let columns = self.column_count();
+        let frozen = self.frozen_rows();
+        let header_row = match self.rows[..frozen].first_mut() {
+            Some(row) => row,
+            None => self.header.get_or_insert_with(Vec::new),
+        };
+        header_row.resize(columns, String::new());
+        header_row.push(header.into());
+        if !self.columns.is_empty() {
+            self.columns.resize(columns + 1, ColumnFormat::default());
+        }
+        self.rows.resize(frozen + got, Vec::new());
+        for (row, value) in self.rows[frozen..].iter_mut().zip(values) {
+            row.resize(columns, String::new());
+            row.push(value);
+        }
+        Ok(())
+    }
+
+    /// Add a column on the left numbering each row, counting up from `start`.
+    ///
+    /// If the table has a header, or a frozen first row, the new column's header is `#`.
+    pub fn number_rows(&mut self, start: usize) -> &mut Self {
+        let frozen = self.frozen_rows();
+        let header = match self.header {
+            Some(ref mut header) => Some(header),
+            None => self.rows[..frozen].first_mut(),
+        };
+        if let Some(header) = header {
+            header.insert(0, "#".to_string());
+        }
+        if !self.columns.is_empty() {
+            self.columns.insert(
+                0,
+                ColumnFormat {
+                    alignment: Alignment::default(),
+                    formatter: None,
+                    fixed_width: None,
+                },
+            );
+        }
+        if !self.kept_widths.is_empty() {
+            self.kept_widths.insert(0, KeptWidth::default());
+        }
+        for (idx, row) in self.rows[frozen..].iter_mut().enumerate() {
+            row.insert(0, (start + idx).to_string());
+        }
+        self
+    }
+
+    /// Replace every cell in column `col` with `f(cell)`. The header is left as it is.
+    ///
+    /// Rows that are too short to have the column are skipped.
+    ///
+    /// ```
+    /// let mut table = text_tables::Table::new();
+    /// table
+    ///     .add_row(["tea", "150"])
+    ///     .add_row(["cake", "320"])
+    ///     .map_column(1, |cell| format!("{} kcal", cell));
+    /// assert_eq!(table.to_string(), "\
+    /// +------+----------+
+    /// | tea  | 150 kcal |
+    /// +------+----------+
+    /// | cake | 320 kcal |
+    /// +------+----------+
+    /// ");
+    /// ```
+    pub fn map_column<F>(&mut self, col: usize, mut f: F) -> &mut Self
+    where
+        F: FnMut(&str) -> String,
+    {
+        let frozen = self.frozen_rows();
+        let rows = self.rows[frozen..].iter_mut();
+        for cell in rows.filter_map(|row| row.get_mut(col)) {
+            *cell = f(cell);
+        }
+        self
+    }
+
+    /// Blank out repeated values in column `col`, so each run of rows with the same value shows
+    /// it only in its first row. The header is left as it is.
+    ///
+    /// ```
+    /// use text_tables::Table;
+    ///
+    /// let mut table = Table::new();
+    /// table
+    ///     .add_row(["fruit", "apple"])
+    ///     .add_row(["fruit", "pear"])
+    ///     .add_row(["veg", "leek"]);
+    /// table.dedup_column(0);
+    /// assert_eq!(table.to_string(), "\
+    /// +-------+-------+
+    /// | fruit | apple |
+    /// +-------+-------+
+    /// |       | pear  |
+    /// +-------+-------+
+    /// | veg   | leek  |
+    /// +-------+-------+
+    /// ");
+    /// ```
+    pub fn dedup_column(&mut self, col: usize) -> &mut Self {
+        let frozen = self.frozen_rows();
+        let mut previous: Option<String> = None;
+        for row in &mut self.rows[frozen..] {
+            match row.get_mut(col) {
+                Some(cell) if previous.as_ref() == Some(cell) => cell.clear(),
+                Some(cell) => previous = Some(cell.clone()),
+                // a row too short to have the column ends the run.
+                None => previous = None,
+            }
+        }
+        self
+    }
+
+    /// Keep only the rows for which `keep` returns `true`, in the same order. The header, and the
+    /// first row if it is frozen with `freeze_header`, are always kept.
+    ///
+    /// ```
+    /// use text_tables::Table;
+    ///
+    /// let mut table = Table::new();
+    /// table
+    ///     .add_header(["name", "age"])
+    ///     .add_row(["Alice", "42"])
+    ///     .add_row(["Bob", "17"]);
+    /// table.filter_rows(|row| row[1].parse::<u32>().unwrap() >= 18);
+    /// assert_eq!(table.row_count(), 1);
+    /// ```
+    pub fn filter_rows<F>(&mut self, mut keep: F) -> &mut Self
+    where
+        F: FnMut(&[String]) -> bool,
+    {
+        let frozen = self.frozen_rows();
+        let mut idx = 0;
+        self.rows.retain(|row| {
+            idx += 1;
+            idx <= frozen || keep(row)
+        });
+        self
+    }
+
+    /// Sort the rows by the text in column `col`. The header is left where it is.
+    ///
+    /// The sort is stable, so rows with the same value keep their order. Rows that are too short
+    /// to have the column sort as if it were empty.
+    pub fn sort_by_column(&mut self, col: usize, ascending: bool) -> &mut Self {
+        self.sort_rows_by(col, ascending, |a, b| a.cmp(b))
+    }
+
+    /// Sort the rows by the number in column `col`. The header is left where it is.
+    ///
+    /// Cells that aren't numbers sort after all those that are, in text order.
+    pub fn sort_by_column_numeric(&mut self, col: usize, ascending: bool) -> &mut Self {
+        self.sort_rows_by(col, ascending, |a, b| {
+            match (a.trim().parse::<f64>(), b.trim().parse::<f64>()) {
+                (Ok(a), Ok(b)) => a.partial_cmp(&b).unwrap_or(Ordering::Equal),
+                (Ok(_), Err(_)) => Ordering::Less,
+                (Err(_), Ok(_)) => Ordering::Greater,
+                (Err(_), Err(_)) => a.cmp(b),
+            }
+        })
+    }
+
+    /// Sort the rows by comparing the cells in column `col`.
+    fn sort_rows_by<F>(&mut self, col: usize, ascending: bool, compare: F) -> &mut Self
+    where
+        F: Fn(&str, &str) -> Ordering,
+    {
+        fn cell(row: &[String], col: usize) -> &str {
+            row.get(col).map_or("", |cell| &cell[..])
+        }
+        let frozen = self.frozen_rows();
+        self.rows[frozen..].sort_by(|a, b| {
+            let ordering = compare(cell(a, col), cell(b, col));
+            if ascending {
+                ordering
+            } else {
+                ordering.reverse()
+            }
+        });
+        self
+    }
+
+    /// Insert a row before the row at `index`, moving the rows after it down.
+    ///
+    /// The header, or a frozen first row, is not counted, so `0` inserts the row just below it, and
+    /// an `index` equal to the number of rows adds it at the bottom.
+    ///
+    /// # Errors
+    ///
+    /// Fails if `index` is greater than the number of rows.
+    pub fn insert_row<R, C>(&mut self, index: usize, row: R) -> Result<(), TableError>
+    where
+        R: IntoIterator<Item = C>,
+        C: Display,
+    {
+        let len = self.row_count();
+        if index > len {
+            return Err(TableError::RowOutOfBounds { index, len });
+        }
+        let frozen = self.frozen_rows();
+        self.rows.insert(frozen + index, format_row(row));
+        Ok(())
+    }
+
+    /// Remove the row at `index`, moving the rows after it up. The header, or a frozen first row,
+    /// is not counted.
+    ///
+    /// # Errors
+    ///
+    /// Fails if there is no row at `index`.
+    pub fn remove_row(&mut self, index: usize) -> Result<(), TableError> {
+        let len = self.row_count();
+        if index >= len {
+            return Err(TableError::RowOutOfBounds { index, len });
+        }
+        let frozen = self.frozen_rows();
+        self.rows.remove(frozen + index);
+        Ok(())
+    }
+
+    /// Get the text of the cell in column `col` of the row at `row`, or `None` if there is no such
+    /// cell. The header, or a frozen first row, is not counted.
+    pub fn get_cell(&self, row: usize, col: usize) -> Option<&str> {
+        self.data_rows().get(row)?.get(col).map(|cell| &cell[..])
+    }
+
+    /// Replace the cell in column `col` of the row at `row`. The header, or a frozen first row, is
+    /// not counted.
+    ///
+    /// A wider value widens its column. A narrower one leaves the column as wide as it was, even
+    /// if the old value was the widest in the column, until `shrink_columns` is called.
+    ///
+    /// # Errors
+    ///
+    /// Fails if there is no row at `row`, or the row has no such column.
+    pub fn set_cell<C: Display>(
+        &mut self,
+        row: usize,
+        col: usize,
+        value: C,
+    ) -> Result<(), TableError> {
+        let (len, frozen) = (self.row_count(), self.frozen_rows());
+        let cells = match self.rows[frozen..].get_mut(row) {
+            Some(cells) => cells,
+            None => return Err(TableError::RowOutOfBounds { index: row, len }),
+        };
+        let len = cells.len();
+        let old = match cells.get_mut(col) {
+            Some(cell) => mem::replace(cell, value.to_string()),
+            None => return Err(TableError::ColumnIndexOutOfRange { index: col, len }),
+        };
+        if self.kept_widths.len() <= col {
+            self.kept_widths.resize(col + 1, KeptWidth::default());
+        }
+        self.kept_widths[col] = self.kept_widths[col].max(KeptWidth::of(&old));
+        Ok(())
+    }
+
+    /// Let columns get narrower again after `set_cell` has replaced their widest cells, so each
+    /// column is only as wide as the cells it has now.
+    pub fn shrink_columns(&mut self) -> &mut Self {
+        self.kept_widths.clear();
+        self
+    }
+
+    /// Swap the rows at `a` and `b`. The header, or a frozen first row, is not counted.
+    ///
+    /// # Errors
+    ///
+    /// Fails if there is no row at `a` or `b`.
+    pub fn swap_rows(&mut self, a: usize, b: usize) -> Result<(), TableError> {
+        let len = self.row_count();
+        if let Some(index) = [a, b].iter().cloned().find(|&index| index >= len) {
+            return Err(TableError::RowOutOfBounds { index, len });
+        }
+        let frozen = self.frozen_rows();
+        self.rows[frozen..].swap(a, b);
+        Ok(())
+    }
+
+    /// Swap columns `a` and `b`, in the header and every row.
+    ///
+    /// Rows too short to have both columns are padded with empty cells first.
+    ///
+    /// # Errors
+    ///
+    /// Fails if `a` or `b` is not less than the number of columns.
+    pub fn swap_columns(&mut self, a: usize, b: usize) -> Result<(), TableError> {
+        let len = self.column_count();
+        if let Some(index) = [a, b].iter().cloned().find(|&index| index >= len) {
+            return Err(TableError::ColumnIndexOutOfRange { index, len });
+        }
+        let needed = cmp::max(a, b) + 1;
+        for row in self.header.iter_mut().chain(self.rows.iter_mut()) {
+            if row.len() < needed {
+                row.resize(needed, String::new());
+            }
+            row.swap(a, b);
+        }
+        if !self.columns.is_empty() {
+            if self.columns.len() < needed {
+                self.columns.resize(needed, ColumnFormat::default());
+            }
+            self.columns.swap(a, b);
+        }
+        if !self.kept_widths.is_empty() {
+            if self.kept_widths.len() < needed {
+                self.kept_widths.resize(needed, KeptWidth::default());
+            }
+            self.kept_widths.swap(a, b);
+        }
+        Ok(())
+    }
+
+    /// Make a new table with the rows of `other` added below the rows of this one.
+    ///
+    /// The new table has this table's header and column definitions. The header of `other`, or its
+    /// frozen first row, is left out.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the tables have different numbers of columns, unless one of them is empty.
+    pub fn append(&self, other: &Table) -> Result<Table, TableError> {
+        let (expected, got) = (self.column_count(), other.column_count());
+        if expected != got && expected != 0 && got != 0 {
+            return Err(TableError::IncompatibleColumnCounts { expected, got });
+        }
+        let mut table = self.clone();
+        table.rows.extend(other.data_rows().iter().cloned());
+        Ok(table)
+    }
+
+    /// Make a new table with the columns of `other` added to the right of the columns of this one.
+    ///
+    /// Each row of the new table is the matching row of this table followed by the matching row
+    /// of `other`, and the same goes for the headers. If only one table has a header, the other's
+    /// part of it is left blank. A frozen first row counts as a header, and the joined header is
+    /// only a frozen first row itself if neither table has a header added with `add_header`.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the tables have different numbers of rows, unless one of them is empty.
+    pub fn join_columns(&self, other: &Table) -> Result<Table, TableError> {
+        if self.is_empty() {
+            return Ok(other.clone());
+        } else if other.is_empty() {
+            return Ok(self.clone());
+        }
+        let (expected, got) = (self.row_count(), other.row_count());
+        if expected != got {
+            return Err(TableError::IncompatibleRowCounts { expected, got });
+        }
+        let (left_columns, right_columns) = (self.column_count(), other.column_count());
+        let join = |left: Option<&Vec<String>>, right: Option<&Vec<String>>| {
+            let mut row = left.cloned().unwrap_or_default();
+            row.resize(left_columns, String::new());
+            row.extend(right.cloned().unwrap_or_default());
+            row.resize(left_columns + right_columns, String::new());
+            row
+        };
+        let header = match (self.header_row(), other.header_row()) {
+            (None, None) => None,
+            (left, right) => Some(join(left, right)),
+        };
+        let mut rows = self
+            .data_rows()
+            .iter()
+            .zip(other.data_rows())
+            .map(|(left, right)| join(Some(left), Some(right)))
+            .collect::<Vec<_>>();
+        // if neither side has a header of its own, the joined header stays a frozen first row.
+        let (header, header_frozen) = match header {
+            Some(header) if self.header.is_none() && other.header.is_none() => {
+                rows.insert(0, header);
+                (None, true)
+            }
+            Some(header) => (Some(header), false),
+            None => (None, self.header_frozen),
+        };
+        let mut columns = Vec::new();
+        if !self.columns.is_empty() || !other.columns.is_empty() {
+            columns = self.columns.clone();
+            columns.resize(left_columns, ColumnFormat::default());
+            columns.extend(other.columns.iter().cloned());
+        }
+        let mut kept_widths = Vec::new();
+        if !self.kept_widths.is_empty() || !other.kept_widths.is_empty() {
+            kept_widths = self.kept_widths.clone();
+            kept_widths.resize(left_columns, KeptWidth::default());
+            kept_widths.extend(other.kept_widths.iter().cloned());
+        }
+        Ok(Table {
+            header,
+            rows,
+            columns,
+            header_frozen,
+            kept_widths,
+        })
+    }
+
+    /// Make a new table with just the given columns, in the given order.
+    ///
+    /// Columns can be reordered, or repeated by giving their index more than once. Cells missing
+    /// from short rows are left empty.
+    ///
+    /// ```
+    /// let mut table = text_tables::Table::new();
+    /// table.add_header(["id", "name", "age"]).add_row(["1", "Alice", "42"]);
+    /// let picked = table.select_columns(&[1, 0]).unwrap();
+    /// assert_eq!(picked.to_string(), "\
+    /// +-------+----+
+    /// | name  | id |
+    /// +=======+====+
+    /// | Alice | 1  |
+    /// +-------+----+
+    /// ");
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Fails if any index is not less than the number of columns.
+    pub fn select_columns(&self, indices: &[usize]) -> Result<Table, TableError> {
+        let len = self.column_count();
+        if let Some(&index) = indices.iter().find(|&&index| index >= len) {
+            return Err(TableError::ColumnIndexOutOfRange { index, len });
+        }
+        let select = |row: &Vec<String>| {
+            indices
+                .iter()
+                .map(|&idx| row.get(idx).cloned().unwrap_or_default())
+                .collect::<Vec<_>>()
+        };
+        let columns = if self.columns.is_empty() {
+            Vec::new()
+        } else {
+            indices
+                .iter()
+                .map(|&idx| self.columns.get(idx).cloned().unwrap_or_default())
+                .collect()
+        };
+        let kept_widths = if self.kept_widths.is_empty() {
+            Vec::new()
+        } else {
+            indices
+                .iter()
+                .map(|&idx| self.kept_widths.get(idx).cloned().unwrap_or_default())
+                .collect()
+        };
+        Ok(Table {
+            header: self.header.as_ref().map(select),
+            rows: self.rows.iter().map(select).collect(),
+            columns,
+            header_frozen: self.header_frozen,
+            kept_widths,
+        })
+    }
+
+    /// Make a new table with a column for each distinct value in column `key_col`, filled with the
+    /// values from column `value_col`.
+    ///
+    /// The new columns are in the order their keys first appear, and the keys become the header.
+    /// Without a `group_col` all the values go in a single row. Otherwise there is a row for each
+    /// distinct value in `group_col`, which is shown in the first column, and a key with no value
+    /// in a group leaves its cell empty. If a key appears more than once in the same group, the
+    /// last value is used. A table without rows pivots to an empty table. A frozen first row is
+    /// used as the header.
+    ///
+    /// ```
+    /// let mut table = text_tables::Table::new();
+    /// table
+    ///     .add_header(["day", "fruit", "sold"])
+    ///     .add_row(["mon", "apple", "3"])
+    ///     .add_row(["mon", "pear", "1"])
+    ///     .add_row(["tue", "apple", "4"]);
+    /// let pivoted = table.pivot(1, 2, Some(0)).unwrap();
+    /// assert_eq!(pivoted.to_string(), "\
+    /// +-----+-------+------+
+    /// | day | apple | pear |
+    /// +=====+=======+======+
+    /// | mon | 3     | 1    |
+    /// +-----+-------+------+
+    /// | tue | 4     |      |
+    /// +-----+-------+------+
+    /// ");
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Fails if any of the columns is not less than the number of columns.
+    pub fn pivot(
+        &self,
+        key_col: usize,
+        value_col: usize,
+        group_col: Option<usize>,
+    ) -> Result<Table, TableError> {
+        let len = self.column_count();
+        let indices = [Some(key_col), Some(value_col), group_col];
+        if let Some(index) = indices
+            .iter()
+            .flatten()
+            .cloned()
+            .find(|&index| index >= len)
+        {
+            return Err(TableError::ColumnIndexOutOfRange { index, len });
+        }
+        if self.data_rows().is_empty() {
+            return Ok(Table::new());
+        }
+        let cell = |row: &[String], col: usize| row.get(col).cloned().unwrap_or_default();
+
+        // the keys and groups in the order they first appear, with the index of each.
+        let mut keys: Vec<String> = Vec::new();
+        let mut key_indices: HashMap<String, usize> = HashMap::new();
+        // each group's value, and the value for each key so far.
+        let mut groups: Vec<(String, Vec<Option<String>>)> = Vec::new();
+        let mut group_indices: HashMap<String, usize> = HashMap::new();
+        for row in self.data_rows() {
+            let key = cell(row, key_col);
+            let key_idx = *key_indices.entry(key).or_insert_with_key(|key| {
+                keys.push(key.clone());
+                keys.len() - 1
+            });
+            let group = group_col.map(|col| cell(row, col)).unwrap_or_default();
+            let group_idx = *group_indices.entry(group).or_insert_with_key(|group| {
+                groups.push((group.clone(), Vec::new()));
+                groups.len() - 1
+            });
+            let values = &mut groups[group_idx].1;
+            if values.len() <= key_idx {
+                values.resize(key_idx + 1, None);
+            }
+            values[key_idx] = Some(cell(row, value_col));
+        }
+
+        let group_header = group_col.map(|col| match self.header_row() {
+            Some(header) => cell(header, col),
+            None => String::new(),
+        });
+        let rows = groups
+            .into_iter()
+            .map(|(group, mut values)| {
+                values.resize(keys.len(), None);
+                let values = values.into_iter().map(Option::unwrap_or_default);
+                group_col.map(|_| group).into_iter().chain(values).collect()
+            })
+            .collect();
+        Ok(Table {
+            header: Some(group_header.into_iter().chain(keys).collect()),
+            rows,
+            columns: Vec::new(),
+            header_frozen: false,
+            kept_widths: Vec::new(),
+        })
+    }
+
+    /// Merge a range of rows into one, putting the cells in each column on separate lines.
+    ///
+    /// The header, or a frozen first row, is not counted, so `0` is the first row after it. See
+    /// `merge_rows_with` for details.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the range ends past the last row.
+    pub fn merge_rows(&mut self, rows: Range<usize>) -> Result<(), TableError> {
+        self.merge_rows_with(rows, "\n")
+    }
+
+    /// Merge a range of rows into one, joining the cells in each column with `joiner`.
+    ///
+    /// Every cell is kept, even when a column has the same value in each row. An empty range leaves
+    /// the table as it is.
+    ///
+    /// ```
+    /// let mut table = text_tables::Table::new();
+    /// table
+    ///     .add_row(["milk", "1"])
+    ///     .add_row(["sugar", "2"])
+    ///     .add_row(["lemon", "0"]);
+    /// table.merge_rows_with(0..2, ", ").unwrap();
+    /// assert_eq!(table.to_string(), "\
+    /// +-------------+------+
+    /// | milk, sugar | 1, 2 |
+    /// +-------------+------+
+    /// | lemon       | 0    |
+    /// +-------------+------+
+    /// ");
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Fails if the range ends past the last row.
+    pub fn merge_rows_with(&mut self, rows: Range<usize>, joiner: &str) -> Result<(), TableError> {
+        let len = self.row_count();
+        if rows.end > len {
+            return Err(TableError::RowOutOfBounds {
+                index: rows.end - 1,
+                len,
+            });
+        }
+        if rows.start >= rows.end {
+            return Ok(());
+        }
+        let frozen = self.frozen_rows();
+        let start = frozen + rows.start;
+        let merging = self
+            .rows
+            .drain(start..frozen + rows.end)
+            .collect::<Vec<_>>();
+        let columns = merging.iter().map(Vec::len).max().unwrap_or(0);
+        let merged = (0..columns)
+            .map(|col| {
+                merging
+                    .iter()
+                    .filter_map(|row| row.get(col))
+                    .map(|cell| &cell[..])
+                    .collect::<Vec<_>>()
+                    .join(joiner)
+            })
+            .collect();
+        self.rows.insert(start, merged);
+        Ok(())
+    }
+
+    /// Get the number of columns, which is the length of the longest row or the header.
+    pub fn column_count(&self) -> usize {
+        self.all_rows().map(Vec::len).max().unwrap_or(0)
+    }
+
+    /// Get the number of rows, not counting the header or a frozen first row.
+    pub fn row_count(&self) -> usize {
+        self.data_rows().len()
+    }
+
+    /// Get the number of rows, counting the header or a frozen first row if there is one.
+    pub fn row_count_with_header(&self) -> usize {
+        self.rows.len() + self.header.iter().count()
+    }
+
+    /// Swap the rows and columns of the table.
+    ///
+    /// A header, if there is one, becomes the first column of the new table, which has no
+    /// header.
+    ///
+    /// # Errors
+    ///
+    /// Fails if all rows are not the same length.
+    pub fn transpose(&self) -> Result<Table, TableError> {
+        let rows = self
+            .header
+            .iter()
+            .chain(self.rows.iter())
+            .cloned()
+            .collect::<Vec<_>>();
+        check_row_lengths(&rows)?;
+        Ok(Table {
+            header: None,
+            rows: transpose_rows(rows),
+            columns: Vec::new(),
+            header_frozen: false,
+            kept_widths: Vec::new(),
+        })
+    }
+
+    /// Render the table to a writer
+    ///
+    /// The table is built up in memory and written in one go, so there is no need to wrap
+    /// `writer` in a `BufWriter`.
+    ///
+    /// # Errors
+    ///
+    /// Fails if all rows are not the same length, or if writing fails.
+    pub fn render<W: io::Write>(&self, writer: &mut W) -> Result<(), TableError> {
+        write_io(writer, |writer| self.render_fmt(writer))
+    }
+
+    /// Render the table to a formatter
+    ///
+    /// # Errors
+    ///
+    /// Fails if all rows are not the same length, or if writing fails.
+    pub fn render_fmt<W: fmt::Write>(&self, writer: &mut W) -> Result<(), TableError> {
+        self.write_table(writer, &Options::default())
+    }
+
+    /// Render the table to a writer, using the given options
+    ///
+    /// If `options.header_row` is set and no header has been added, the first row is used as
+    /// the header. If `options.footer_row` is set, the last row is the footer.
+    ///
+    /// Short rows are padded with empty cells if `options.allow_ragged` is set.
+    ///
+    /// # Errors
+    ///
+    /// Fails if all rows are not the same length, or if writing fails.
+    pub fn render_with_options<W: io::Write>(
+        &self,
+        writer: &mut W,
+        options: &Options,
+    ) -> Result<(), TableError> {
+        write_io(writer, |writer| self.write_table(writer, options))
+    }
+
+    /// Write the table using the given options.
+    pub(crate) fn write_table<W: fmt::Write>(
+        &self,
+        writer: &mut W,
+        options: &Options,
+    ) -> Result<(), TableError> {
+        let (table, options) = self.prepare_visible(options);
+        table.write_lines(writer, &options)
+    }
+
+    /// Prepare the table with `prepare`, then take out any hidden columns.
+    fn prepare_visible<'a>(&'a self, options: &'a Options) -> (Cow<'a, Table>, Cow<'a, Options>) {
+        let options = self.column_options(options);
+        let table = self.prepare(&options);
+        if options.hidden_columns.is_empty() {
+            return (table, options);
+        }
+        let hidden = &options.hidden_columns;
+        let visible = |row: &[String]| {
+            row.iter()
+                .enumerate()
+                .filter(|&(idx, _)| !hidden.contains(&idx))
+                .map(|(_, cell)| cell.clone())
+                .collect::<Vec<_>>()
+        };
+        let table = Table {
+            header: table.header.as_ref().map(|header| visible(header)),
+            rows: table.rows.iter().map(|row| visible(row)).collect(),
+            columns: table
+                .columns
+                .iter()
+                .enumerate()
+                .filter(|&(idx, _)| !hidden.contains(&idx))
+                .map(|(_, column)| column.clone())
+                .collect(),
+            header_frozen: table.header_frozen,
+            kept_widths: Vec::new(),
+        };
+        let options = options.without_hidden_columns();
+        (Cow::Owned(table), Cow::Owned(options))
+    }
+
+    /// Use the alignments from any `ColumnDef`s, unless `options` has its own, and keep columns at
+    /// least as wide as the cells `set_cell` replaced.
+    fn column_options<'a>(&self, options: &'a Options) -> Cow<'a, Options> {
+        let mut options = Cow::Borrowed(options);
+        if !self.columns.is_empty() && options.alignments.is_empty() {
+            options.to_mut().alignments =
+                self.columns.iter().map(|column| column.alignment).collect();
+        }
+        if !self.kept_widths.is_empty() {
+            let strip_ansi = options.strip_ansi_for_width;
+            let min_widths = &mut options.to_mut().min_column_widths;
+            if min_widths.len() < self.kept_widths.len() {
+                min_widths.resize(self.kept_widths.len(), 0);
+            }
+            for (min, &kept) in min_widths.iter_mut().zip(&self.kept_widths) {
+                *min = cmp::max(*min, kept.get(strip_ansi));
+            }
+        }
+        options
+    }
+
+    /// Get the width and height the table would have if it were rendered.
+    pub(crate) fn dimensions(&self, options: &Options) -> Result<(usize, usize), TableError> {
+        let (table, options) = self.prepare_visible(options);
+        let options = &*options;
+        let Layout {
+            header,
+            rows,
+            widths,
+        } = table.layout(options)?;
+        let (mut width, mut height) = match options.title {
+            Some(ref title) => (options.text_width(title), 1),
+            None => (0, 0),
+        };
+        height += 2 * options.outer_padding_lines;
+        if widths.is_empty() || widths[0] == 0 {
+            return Ok((width, height));
+        }
+        width = cmp::max(width, table_width(&widths, options));
+        let all_rows = header.into_iter().chain(rows.iter());
+        height += all_rows.map(|row| row_height(row)).sum::<usize>();
+        if options.style.borders && options.border_mode != BorderMode::VerticalOnly {
+            let before_footer = options.footer_row && rows.len() > 1;
+            let footer = match options.border_mode {
+                BorderMode::HeaderOnly => 0,
+                _ => before_footer as usize,
+            };
+            // the lines between the other rows.
+            let inner = rows.len().saturating_sub(1 + before_footer as usize);
+            let separators = footer
+                + (0..inner)
+                    .filter(|&idx| {
+                        has_header_separator(options, header.is_some(), idx)
+                            || has_separator(options, idx)
+                    })
+                    .count();
+            // without any rows, the line below the header is the bottom edge.
+            let edges = match (options.outer_borders(), rows.is_empty()) {
+                (false, _) => 0,
+                (true, true) => 1,
+                (true, false) if options.border_mode == BorderMode::HeaderOnly => 1,
+                (true, false) => 2,
+            };
+            height += separators + header.map_or(0, |_| 1) + edges;
+        }
+        Ok((width, height))
+    }
+
+    /// Guess how many bytes the table takes up when rendered with the default options, so the
+    /// buffer can be allocated up front. See `estimate_buffer_size`.
+    pub(crate) fn estimate_size(&self) -> usize {
+        let options = Options::default();
+        let columns = self.all_rows().map(Vec::len).max().unwrap_or(0);
+        // lines are padded out to their column's width with spaces, so any bytes beyond a line's
+        // display width are extra.
+        let mut widths = vec![0; columns];
+        let mut extra = vec![0; columns];
+        for row in self.all_rows() {
+            for (col, cell) in row.iter().enumerate() {
+                for line in cell.lines() {
+                    // printable ASCII is one column a byte, and is most text, so don't measure it.
+                    let printable = line.bytes().all(|b| (b' '..=b'~').contains(&b));
+                    let width = if printable {
+                        line.len()
+                    } else {
+                        options.text_width(line)
+                    };
+                    widths[col] = cmp::max(widths[col], width);
+                    extra[col] = cmp::max(extra[col], line.len().saturating_sub(width));
+                }
+            }
+        }
+        for (width, extra) in widths.iter_mut().zip(extra) {
+            *width += extra;
+        }
+        let lines = self.all_rows().map(|row| row_height(row)).sum();
+        estimate_buffer_size(&widths, lines, options.padding)
+    }
+
+    /// Get the cells ready to be laid out, by padding ragged rows and fitting cells to their
+    /// column's maximum width, if the options ask for it.
+    fn prepare(&self, options: &Options) -> Cow<'_, Table> {
+        let mut table = Cow::Borrowed(self);
+        if options.allow_ragged {
+            let columns = self.all_rows().map(Vec::len).max().unwrap_or(0);
+            if self.all_rows().any(|row| row.len() != columns) {
+                let table = table.to_mut();
+                for row in table.header.iter_mut().chain(table.rows.iter_mut()) {
+                    row.resize(columns, String::new());
+                }
+            }
+        }
+        let has_fixed = self
+            .columns
+            .iter()
+            .any(|column| column.fixed_width.is_some());
+        if has_fixed || options.has_max_widths() {
+            let marker = &options.truncation_marker;
+            table = Cow::Owned(table.map_cells(|col, cell| {
+                match (self.fixed_width(col), options.max_column_width(col)) {
+                    (Some(width), _) => truncate(cell, width, marker),
+                    (None, Some(max_width)) => match options.overflow {
+                        Overflow::Wrap => wrap(cell, max_width),
+                        Overflow::Truncate => truncate(cell, max_width, marker),
+                    },
+                    (None, None) => cell.to_string(),
+                }
+            }));
+        }
+        table
+    }
+
+    /// Get the fixed width of a column, if it was given one by its `ColumnDef`.
+    fn fixed_width(&self, column: usize) -> Option<usize> {
+        self.columns
+            .get(column)
+            .and_then(|column| column.fixed_width)
+    }
+
+    /// Iterate over the header, if there is one, then the rows.
+    fn all_rows(&self) -> impl Iterator<Item = &Vec<String>> {
+        self.header.iter().chain(self.rows.iter())
+    }
+
+    /// Split off the header, and work out the width of each column.
+    fn layout(&self, options: &Options) -> Result<Layout<'_>, TableError> {
+        let (header, rows) = match self.header {
+            Some(ref header) => (Some(header), &self.rows[..]),
+            None if options.header_row && !self.rows.is_empty() => {
+                (Some(&self.rows[0]), &self.rows[1..])
+            }
+            None => (None, &self.rows[..]),
+        };
+        let mut widths = widths(header.into_iter().chain(rows.iter()), options)?;
+        for (idx, width) in widths.iter_mut().enumerate() {
+            *width = match self.fixed_width(idx) {
+                Some(fixed_width) => fixed_width,
+                None => cmp::max(*width, options.min_column_width(idx)),
+            };
+        }
+        Ok(Layout {
+            header,
+            rows,
+            widths,
+        })
+    }
+
+    /// Write the table exactly as it is, once all cells are ready.
+    fn write_lines<W: fmt::Write>(
+        &self,
+        writer: &mut W,
+        options: &Options,
+    ) -> Result<(), TableError> {
+        let style = &options.style;
+        let Layout {
+            header,
+            rows,
+            widths,
+        } = self.layout(options)?;
+
+        write_padding_lines(writer, options)?;
+        if let Some(ref title) = options.title {
+            render_title_line(writer, &widths, options, title)?;
+        }
+        render_border_line(writer, &widths, options, style.horizontal, BorderLine::Top)?;
+        if let Some(header) = header {
+            render_text_line(writer, &widths, options, header)?;
+            let line = if rows.is_empty() && options.outer_borders() {
+                BorderLine::Bottom
+            } else {
+                BorderLine::Middle
+            };
+            render_border_line(writer, &widths, options, style.header_horizontal, line)?;
+        }
+        for (idx, row) in rows.iter().enumerate() {
+            let row_style = options.row_style(idx);
+            let colour = match row_style {
+                Some(ref row_style) => Some((&row_style.prefix[..], &row_style.suffix[..])),
+                None => match options.zebra_stripe {
+                    Some((ref even, _)) if idx % 2 == 0 => Some((&even[..], ANSI_RESET)),
+                    Some((_, ref odd)) => Some((&odd[..], ANSI_RESET)),
+                    None => None,
+                },
+            };
+            render_coloured_text_line(writer, &widths, options, row, colour)?;
+            let last = idx + 1 == rows.len();
+            let before_footer = options.footer_row && idx + 2 == rows.len();
+            let header_only = options.border_mode == BorderMode::HeaderOnly;
+            let border = if last {
+                Some((style.horizontal, BorderLine::Bottom)).filter(|_| !header_only)
+            } else if before_footer {
+                Some((style.header_horizontal, BorderLine::Middle)).filter(|_| !header_only)
+            } else if has_header_separator(options, header.is_some(), idx) {
+                Some((style.header_horizontal, BorderLine::Middle))
+            } else if has_separator(options, idx) {
+                Some((style.horizontal, BorderLine::Middle))
+            } else {
+                None
+            };
+            if let Some((horiz, line)) = border {
+                render_border_line(writer, &widths, options, horiz, line)?;
+            }
+        }
+        write_padding_lines(writer, options)?;
+
+        Ok(())
+    }
+}
+
+/// Whether there is a border line below the given row, when it isn't the last row or the one
+/// before the footer.
+fn has_separator(options: &Options, row: usize) -> bool {
+    match options.border_mode {
+        BorderMode::Full | BorderMode::InnerOnly => true,
+        _ => options.is_separator(row),
+    }
+}
+
+/// Write the blank lines that go above and below the table.
+fn write_padding_lines<W: fmt::Write>(writer: &mut W, options: &Options) -> fmt::Result {
+    for _ in 0..options.outer_padding_lines {
+        writer.write_str(options.line_ending.as_str())?;
+    }
+    Ok(())
+}
+
+/// Whether the given row is followed by a header line even though it isn't the header, because
+/// `options.header_separator` is set.
+fn has_header_separator(options: &Options, has_header: bool, row: usize) -> bool {
+    options.header_separator && !has_header && row == 0
+}
+
+/// The parts of a table that are needed to render it.
+struct Layout<'a> {
+    header: Option<&'a Vec<String>>,
+    rows: &'a [Vec<String>],
+    widths: Vec<usize>,
+}
+
+/// Renders the table with the default options, so it can be used with `println!` and friends.
+///
+/// Formatting fails if all rows are not the same length.
+impl fmt::Display for Table {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.render_fmt(f).map_err(|_| fmt::Error)
+    }
+}
+
+impl<R, C> FromIterator<R> for Table
+where
+    R: IntoIterator<Item = C>,
+    C: Display,
+{
+    fn from_iter<I: IntoIterator<Item = R>>(rows: I) -> Self {
+        Table {
+            header: None,
+            rows: rows.into_iter().map(format_row).collect(),
+            columns: Vec::new(),
+            header_frozen: false,
+            kept_widths: Vec::new(),
+        }
+    }
+}
+
+impl Table {
+    /// Whether the table has no header and no rows.
+    fn is_empty(&self) -> bool {
+        self.header.is_none() && self.rows.is_empty()
+    }
+
+    /// Create a copy of the table with `f(column, cell)` applied to every cell.
+    fn map_cells<F>(&self, f: F) -> Table
+    where
+        F: Fn(usize, &str) -> String,
+    {
+        let map_row = |row: &Vec<String>| {
+            row.iter()
+                .enumerate()
+                .map(|(col, cell)| f(col, cell))
+                .collect()
+        };
+        Table {
+            header: self.header.as_ref().map(&map_row),
+            rows: self.rows.iter().map(&map_row).collect(),
+            columns: self.columns.clone(),
+            header_frozen: self.header_frozen,
+            kept_widths: self.kept_widths.clone(),
+        }
+    }
+}
+
+/// Swap the rows and columns of some rows, which must all be the same length.
+pub(crate) fn transpose_rows(rows: Vec<Vec<String>>) -> Vec<Vec<String>> {
+    let columns = rows.first().map(Vec::len).unwrap_or(0);
+    let mut transposed = vec![Vec::with_capacity(rows.len()); columns];
+    for row in rows {
+        for (column, cell) in transposed.iter_mut().zip(row) {
+            column.push(cell);
+        }
+    }
+    transposed
+}
+
+/// Format each cell in a row using its `Display` impl.
+fn format_row<R, C>(row: R) -> Vec<String>
+where
+    R: IntoIterator<Item = C>,
+    C: Display,
+{
+    row.into_iter().map(|cell| cell.to_string()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Table;
+    use TableError;
+
+    fn render(table: &Table) -> String {
+        let mut out = String::new();
+        table.render_fmt(&mut out).unwrap();
+        out
+    }
+
+    #[test]
+    fn rows() {
+        let mut table = Table::new();
+        table.add_row(["single", "line", "a"]);
+        table.add_row(vec!["second".to_string(), "lines".into(), "a".into()]);
+        assert_eq!(
+            render(&table),
+            "\
++--------+-------+---+
+| single | line  | a |
++--------+-------+---+
+| second | lines | a |
++--------+-------+---+
+"
+        );
+    }
+
+    #[test]
+    fn header() {
+        let expected = "\
++------+-----+
+| Name | Age |
++======+=====+
+| Bob  | 7   |
++------+-----+
+| Eve  | 100 |
++------+-----+
+";
+        let mut header_first = Table::new();
+        header_first
+            .add_header(["Name", "Age"])
+            .add_row(["Bob", "7"])
+            .add_row(["Eve", "100"]);
+        assert_eq!(render(&header_first), expected);
+
+        let mut interleaved = Table::new();
+        interleaved
+            .add_row(["Bob", "7"])
+            .add_header(["Name", "Age"])
+            .add_row(["Eve", "100"]);
+        assert_eq!(render(&interleaved), expected);
+    }
+
+    #[test]
+    fn header_only() {
+        let mut table = Table::new();
+        table.add_header([1, 2]);
+        assert_eq!(render(&table), "+---+---+\n| 1 | 2 |\n+===+===+\n");
+    }
+
+    #[test]
+    fn unequal_header_length() {
+        let mut table = Table::new();
+        table.add_header(["a", "b"]).add_row(["c"]);
+        match table.render_fmt(&mut String::new()) {
+            Err(::TableError::UnequalRowLengths {
+                expected: 2,
+                got: 1,
+                row_index: 1,
+            }) => (),
+            other => panic!("unexpected result {:?}", other),
+        }
+    }
+
+    #[test]
+    fn header_row_option() {
+        let mut table = Table::new();
+        table.add_row(["a", "b"]).add_row(["c", "d"]);
+        let options = ::Options {
+            header_row: true,
+            ..::Options::default()
+        };
+        let mut out = Vec::new();
+        table.render_with_options(&mut out, &options).unwrap();
+        assert_eq!(
+            ::std::str::from_utf8(&out).unwrap(),
+            "+---+---+\n| a | b |\n+===+===+\n| c | d |\n+---+---+\n"
+        );
+    }
+
+    #[test]
+    fn compact() {
+        let mut table = Table::new();
+        table
+            .add_header(["name", "n"])
+            .add_row(["a", "1"])
+            .add_row(["b", "2"])
+            .add_row(["c", "3"]);
+        let render_mode = |border_mode| {
+            let options = ::Options {
+                border_mode,
+                ..::Options::default()
+            };
+            let mut out = Vec::new();
+            table.render_with_options(&mut out, &options).unwrap();
+            String::from_utf8(out).unwrap()
+        };
+        assert_eq!(
+            render_mode(::BorderMode::Full),
+            "\
++------+---+
+| name | n |
++======+===+
+| a    | 1 |
++------+---+
+| b    | 2 |
++------+---+
+| c    | 3 |
++------+---+
+"
+        );
+        assert_eq!(
+            render_mode(::BorderMode::Compact),
+            "\
++------+---+
+| name | n |
++======+===+
+| a    | 1 |
+| b    | 2 |
+| c    | 3 |
++------+---+
+"
+        );
+    }
+
+    #[test]
+    fn display() {
+        let data = vec![vec!["single", "line", "a"], vec!["second", "lines", "a"]];
+        let table = data.iter().collect::<Table>();
+        assert_eq!(format!("{}", table), ::render_to_string(&data));
+        assert_eq!(format!("{}", Table::new()), "");
+    }
+
+    #[test]
+    fn number_rows() {
+        let mut table = Table::new();
+        table
+            .add_header(["name"])
+            .add_row(["a"])
+            .add_row(["b"])
+            .number_rows(9);
+        assert_eq!(
+            render(&table),
+            "\
++----+------+
+| #  | name |
++====+======+
+| 9  | a    |
++----+------+
+| 10 | b    |
++----+------+
+"
+        );
+    }
+
+    #[test]
+    fn transpose() {
+        let mut table = Table::new();
+        table.add_header(["", "x", "y"]).add_row(["a", "1", "2"]);
+        let transposed = table.transpose().unwrap();
+        assert_eq!(
+            render(&transposed),
+            "\
++---+---+
+|   | a |
++---+---+
+| x | 1 |
++---+---+
+| y | 2 |
++---+---+
+"
+        );
+
+        let mut ragged = Table::new();
+        ragged.add_row(["a", "b"]).add_row(["c"]);
+        assert!(ragged.transpose().is_err());
+    }
+
+    #[test]
+    fn equality() {
+        let mut table = Table::new();
+        table.add_header(["a"]).add_row([1]);
+        let mut same = Table::new();
+        same.add_row(["1"]).add_header(["a"]);
+        assert_eq!(table, same);
+
+        let mut other_cell = Table::new();
+        other_cell.add_header(["a"]).add_row([2]);
+        assert_ne!(table, other_cell);
+
+        // a header isn't the same as a first row.
+        let no_header = vec![vec!["a"], vec!["1"]].into_iter().collect::<Table>();
+        assert_ne!(table, no_header);
+    }
+
+    #[test]
+    fn footer_row() {
+        let render_footer = |table: &Table| {
+            let options = ::Options {
+                footer_row: true,
+                ..::Options::default()
+            };
+            let mut out = Vec::new();
+            table.render_with_options(&mut out, &options).unwrap();
+            String::from_utf8(out).unwrap()
+        };
+        let mut table = Table::new();
+        table.add_header(["item", "n"]).add_row(["total", "0"]);
+        assert_eq!(
+            render_footer(&table),
+            "\
++-------+---+
+| item  | n |
++=======+===+
+| total | 0 |
++-------+---+
+"
+        );
+
+        let mut table = Table::new();
+        table
+            .add_header(["item", "n"])
+            .add_row(["a", "1"])
+            .add_row(["b", "2"])
+            .add_row(["total", "3"]);
+        assert_eq!(
+            render_footer(&table),
+            "\
++-------+---+
+| item  | n |
++=======+===+
+| a     | 1 |
++-------+---+
+| b     | 2 |
++=======+===+
+| total | 3 |
++-------+---+
+"
+        );
+        assert_eq!(
+            render(&table),
+            "\
++-------+---+
+| item  | n |
++=======+===+
+| a     | 1 |
++-------+---+
+| b     | 2 |
++-------+---+
+| total | 3 |
++-------+---+
+"
+        );
+    }
+
+    #[test]
+    fn allow_ragged() {
+        let mut table = Table::new();
+        table
+            .add_row(["a", "b", "c"])
+            .add_row(["d", "e"])
+            .add_row(["f", "g", "h", "i"]);
+        let mut out = Vec::new();
+        assert!(table.render(&mut out).is_err());
+
+        let options = ::Options {
+            allow_ragged: true,
+            ..::Options::default()
+        };
+        let mut out = Vec::new();
+        table.render_with_options(&mut out, &options).unwrap();
+        assert_eq!(
+            ::std::str::from_utf8(&out).unwrap(),
+            "\
++---+---+---+---+
+| a | b | c |   |
++---+---+---+---+
+| d | e |   |   |
++---+---+---+---+
+| f | g | h | i |
++---+---+---+---+
+"
+        );
+    }
+
+    #[test]
+    fn hidden_columns() {
+        let mut table = Table::new();
+        table
+            .add_header(["id", "name", "age"])
+            .add_row(["1", "Alice", "42"]);
+        let render = |hidden_columns: Vec<usize>| {
+            let options = ::Options {
+                hidden_columns,
+                // refers to the "age" column, wherever it ends up.
+                alignments: vec![::Alignment::Left, ::Alignment::Left, ::Alignment::Right],
+                ..::Options::default()
+            };
+            let mut out = Vec::new();
+            table.render_with_options(&mut out, &options).unwrap();
+            String::from_utf8(out).unwrap()
+        };
+        assert_eq!(
+            render(vec![0]),
+            "+-------+-----+\n| name  | age |\n+=======+=====+\n| Alice |  42 |\n+-------+-----+\n"
+        );
+        assert_eq!(
+            render(vec![2]),
+            "+----+-------+\n| id | name  |\n+====+=======+\n| 1  | Alice |\n+----+-------+\n"
+        );
+        assert_eq!(
+            render(vec![1]),
+            "+----+-----+\n| id | age |\n+====+=====+\n| 1  |  42 |\n+----+-----+\n"
+        );
+        assert_eq!(render(vec![0, 1, 2]), "");
+    }
+
+    #[test]
+    fn add_row_indented() {
+        let mut table = Table::new();
+        table
+            .add_header(["name", "size"])
+            .add_row_indented(["src", "-"], 0)
+            .add_row_indented(["bin", "-"], 1)
+            .add_row_indented(["main.rs", "12"], 2)
+            .add_row_indented(["lib.rs", "40"], 1);
+        assert_eq!(
+            table.to_string(),
+            "\
++-------------+------+
+| name        | size |
++=============+======+
+| src         | -    |
++-------------+------+
+|   bin       | -    |
++-------------+------+
+|     main.rs | 12   |
++-------------+------+
+|   lib.rs    | 40   |
++-------------+------+
+"
+        );
+    }
+
+    #[test]
+    fn counts() {
+        let mut table = Table::new();
+        assert_eq!(
+            (
+                table.column_count(),
+                table.row_count(),
+                table.row_count_with_header()
+            ),
+            (0, 0, 0)
+        );
+        table.add_header(["a", "b"]);
+        assert_eq!(
+            (
+                table.column_count(),
+                table.row_count(),
+                table.row_count_with_header()
+            ),
+            (2, 0, 1)
+        );
+        table.add_row(["2", "x"]).add_row(["1", "y"]);
+        assert_eq!(
+            (
+                table.column_count(),
+                table.row_count(),
+                table.row_count_with_header()
+            ),
+            (2, 2, 3)
+        );
+        table.sort_by_column(0, true);
+        assert_eq!(
+            (
+                table.column_count(),
+                table.row_count(),
+                table.row_count_with_header()
+            ),
+            (2, 2, 3)
+        );
+        table.add_row(["3", "z", "extra"]);
+        assert_eq!((table.column_count(), table.row_count()), (3, 3));
+    }
+
+    #[test]
+    fn insert_and_remove_rows() {
+        let column = |table: &Table| {
+            table
+                .rows
+                .iter()
+                .map(|row| &row[0][..])
+                .collect::<Vec<_>>()
+                .join("")
+        };
+        let mut table = Table::new();
+        table.add_header(["h"]).add_row(["b"]).add_row(["d"]);
+        table.insert_row(0, ["a"]).unwrap();
+        assert_eq!(column(&table), "abd");
+        table.insert_row(3, ["e"]).unwrap();
+        assert_eq!(column(&table), "abde");
+        table.insert_row(2, ["c"]).unwrap();
+        assert_eq!(column(&table), "abcde");
+        match table.insert_row(6, ["x"]) {
+            Err(TableError::RowOutOfBounds { index: 6, len: 5 }) => (),
+            other => panic!("unexpected result: {:?}", other),
+        }
+
+        table.remove_row(0).unwrap();
+        assert_eq!(column(&table), "bcde");
+        table.remove_row(3).unwrap();
+        assert_eq!(column(&table), "bcd");
+        match table.remove_row(3) {
+            Err(TableError::RowOutOfBounds { index: 3, len: 3 }) => (),
+            other => panic!("unexpected result: {:?}", other),
+        }
+        assert_eq!(table.header, Some(vec!["h".to_string()]));
+    }
+
+    #[test]
+    fn get_and_set_cells() {
+        let mut table = Table::new();
+        table
+            .add_header(["name", "n"])
+            .add_row(["a", "1"])
+            .add_row(["bcd", "2"]);
+        assert_eq!(table.get_cell(0, 0), Some("a"));
+        assert_eq!(table.get_cell(1, 1), Some("2"));
+        assert_eq!(table.get_cell(2, 0), None);
+        assert_eq!(table.get_cell(0, 2), None);
+
+        table.set_cell(0, 1, 12345).unwrap();
+        assert_eq!(table.get_cell(0, 1), Some("12345"));
+        assert_eq!(
+            table.to_string(),
+            "\
++------+-------+
+| name | n     |
++======+=======+
+| a    | 12345 |
++------+-------+
+| bcd  | 2     |
++------+-------+
+"
+        );
+        table.set_cell(1, 0, "b").unwrap();
+        assert_eq!(
+            table.to_string(),
+            "\
++------+-------+
+| name | n     |
++======+=======+
+| a    | 12345 |
++------+-------+
+| b    | 2     |
++------+-------+
+"
+        );
+        match table.set_cell(2, 0, "x") {
+            Err(TableError::RowOutOfBounds { index: 2, len: 2 }) => (),
+            other => panic!("unexpected result: {:?}", other),
+        }
+        match table.set_cell(0, 2, "x") {
+            Err(TableError::ColumnIndexOutOfRange { index: 2, len: 2 }) => (),
+            other => panic!("unexpected result: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn set_cell_keeps_width() {
+        let mut table = Table::new();
+        table.add_row(["abcdef", "x"]);
+        table.set_cell(0, 0, "a").unwrap();
+        assert_eq!(
+            table.to_string(),
+            "+--------+---+\n| a      | x |\n+--------+---+\n"
+        );
+        table.set_cell(0, 0, "ab").unwrap();
+        assert_eq!(
+            table.to_string(),
+            "+--------+---+\n| ab     | x |\n+--------+---+\n"
+        );
+
+        // the kept widths move with their columns.
+        table.swap_columns(0, 1).unwrap();
+        assert_eq!(
+            table.to_string(),
+            "+---+--------+\n| x | ab     |\n+---+--------+\n"
+        );
+        assert_eq!(
+            table.select_columns(&[1]).unwrap().to_string(),
+            "+--------+\n| ab     |\n+--------+\n"
+        );
+
+        table.shrink_columns();
+        assert_eq!(table.to_string(), "+---+----+\n| x | ab |\n+---+----+\n");
+    }
+
+    #[test]
+    fn set_cell_keeps_width_strip_ansi() {
+        let options = ::Options {
+            strip_ansi_for_width: true,
+            ..::Options::default()
+        };
+        let mut table = Table::new();
+        table.add_row(["\x1b[31mred\x1b[0m", "x"]);
+        table.set_cell(0, 0, "r").unwrap();
+        let mut out = Vec::new();
+        table.render_with_options(&mut out, &options).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "+-----+---+\n| r   | x |\n+-----+---+\n"
+        );
+    }
+
+    #[test]
+    fn swap_rows() {
+        let column = |table: &Table| table.rows.iter().map(|row| &row[0][..]).collect::<String>();
+        let mut table = Table::new();
+        table
+            .add_header(["h"])
+            .add_row(["a"])
+            .add_row(["b"])
+            .add_row(["c"])
+            .add_row(["d"]);
+        table.swap_rows(0, 3).unwrap();
+        assert_eq!(column(&table), "dbca");
+        table.swap_rows(1, 2).unwrap();
+        assert_eq!(column(&table), "dcba");
+        table.swap_rows(2, 2).unwrap();
+        assert_eq!(column(&table), "dcba");
+        match table.swap_rows(1, 4) {
+            Err(TableError::RowOutOfBounds { index: 4, len: 4 }) => (),
+            other => panic!("unexpected result: {:?}", other),
+        }
+        assert_eq!(table.header, Some(vec!["h".to_string()]));
+    }
+
+    #[test]
+    fn swap_columns() {
+        let table = |rows: &[&[&str]]| {
+            let mut table = Table::new();
+            table.add_header(rows[0]);
+            for row in &rows[1..] {
+                table.add_row(*row);
+            }
+            table
+        };
+        let mut swapped = table(&[&["a", "b", "c"], &["1", "2", "3"], &["4", "5", "6"]]);
+        swapped.swap_columns(0, 2).unwrap();
+        assert_eq!(
+            swapped,
+            table(&[&["c", "b", "a"], &["3", "2", "1"], &["6", "5", "4"]])
+        );
+        swapped.swap_columns(1, 2).unwrap();
+        assert_eq!(
+            swapped,
+            table(&[&["c", "a", "b"], &["3", "1", "2"], &["6", "4", "5"]])
+        );
+        swapped.swap_columns(1, 1).unwrap();
+        assert_eq!(
+            swapped,
+            table(&[&["c", "a", "b"], &["3", "1", "2"], &["6", "4", "5"]])
+        );
+        match swapped.swap_columns(3, 0) {
+            Err(TableError::ColumnIndexOutOfRange { index: 3, len: 3 }) => (),
+            other => panic!("unexpected result: {:?}", other),
+        }
+
+        let mut ragged = table(&[&["a", "b"], &["1"]]);
+        ragged.swap_columns(0, 1).unwrap();
+        assert_eq!(ragged, table(&[&["b", "a"], &["", "1"]]));
+    }
+
+    #[test]
+    fn append() {
+        let mut first = Table::new();
+        first.add_header(["a", "b"]).add_row(["1", "2"]);
+        let mut second = Table::new();
+        second.add_header(["c", "d"]).add_row(["3", "4"]);
+        let mut expected = Table::new();
+        expected
+            .add_header(["a", "b"])
+            .add_row(["1", "2"])
+            .add_row(["3", "4"]);
+        assert_eq!(first.append(&second).unwrap(), expected);
+
+        let mut wide = Table::new();
+        wide.add_row(["1", "2", "3"]);
+        match first.append(&wide) {
+            Err(TableError::IncompatibleColumnCounts {
+                expected: 2,
+                got: 3,
+            }) => (),
+            other => panic!("unexpected result: {:?}", other),
+        }
+
+        assert_eq!(first.append(&Table::new()).unwrap(), first);
+        let mut rows_only = second.clone();
+        rows_only.header = None;
+        assert_eq!(Table::new().append(&second).unwrap(), rows_only);
+    }
+
+    #[test]
+    fn join_columns() {
+        let mut left = Table::new();
+        left.add_header(["a"])
+            .add_row(["1"])
+            .add_row(["2"])
+            .add_row(["3"]);
+        let mut right = Table::new();
+        right
+            .add_header(["b", "c"])
+            .add_row(["x", "y"])
+            .add_row(["z", "w"])
+            .add_row(["v", "u"]);
+        let mut expected = Table::new();
+        expected
+            .add_header(["a", "b", "c"])
+            .add_row(["1", "x", "y"])
+            .add_row(["2", "z", "w"])
+            .add_row(["3", "v", "u"]);
+        assert_eq!(left.join_columns(&right).unwrap(), expected);
+
+        assert_eq!(Table::new().join_columns(&right).unwrap(), right);
+        assert_eq!(left.join_columns(&Table::new()).unwrap(), left);
+
+        let mut short = Table::new();
+        short.add_row(["only"]);
+        match left.join_columns(&short) {
+            Err(TableError::IncompatibleRowCounts {
+                expected: 3,
+                got: 1,
+            }) => (),
+            other => panic!("unexpected result: {:?}", other),
+        }
+        let mut single = Table::new();
+        single.add_row(["left"]);
+        let mut expected = Table::new();
+        expected.add_header(["", "h"]).add_row(["left", "only"]);
+        let mut headed = short.clone();
+        headed.add_header(["h"]);
+        assert_eq!(single.join_columns(&headed).unwrap(), expected);
+    }
+
+    #[test]
+    fn join_columns_frozen() {
+        let frozen = |rows: &[[&str; 1]]| {
+            let mut table = rows.iter().collect::<Table>();
+            table.freeze_header();
+            table
+        };
+        let mut headed = Table::new();
+        headed.add_header(["b"]).add_row(["x"]).add_row(["y"]);
+        let mut plain = Table::new();
+        plain.add_row(["p"]).add_row(["q"]);
+
+        let mut expected = Table::new();
+        expected
+            .add_header(["a", "b"])
+            .add_row(["1", "x"])
+            .add_row(["2", "y"]);
+        let left = frozen(&[["a"], ["1"], ["2"]]);
+        assert_eq!(left.join_columns(&headed).unwrap(), expected);
+
+        let joined = plain.join_columns(&left).unwrap();
+        assert!(joined.is_header_frozen());
+        assert_eq!(
+            joined.rows,
+            vec![vec!["", "a"], vec!["p", "1"], vec!["q", "2"]]
+        );
+
+        let joined = left.join_columns(&frozen(&[["c"], ["3"], ["4"]])).unwrap();
+        assert!(joined.is_header_frozen());
+        assert_eq!(joined.header, None);
+        assert_eq!(
+            joined.rows,
+            vec![vec!["a", "c"], vec!["1", "3"], vec!["2", "4"]]
+        );
+        assert_eq!(joined.row_count(), 2);
+
+        match left.join_columns(&frozen(&[["c"], ["3"]])) {
+            Err(TableError::IncompatibleRowCounts {
+                expected: 2,
+                got: 1,
+            }) => (),
+            other => panic!("unexpected result: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn select_columns() {
+        let mut table = Table::new();
+        table
+            .add_header(["a", "b", "c"])
+            .add_row(["1", "2", "3"])
+            .add_row(["4", "5", "6"]);
+        let select = |indices: &[usize]| {
+            let mut expected = Table::new();
+            let pick = |row: &[&str]| {
+                indices
+                    .iter()
+                    .map(|&idx| row[idx].to_string())
+                    .collect::<Vec<_>>()
+            };
+            expected
+                .add_header(pick(&["a", "b", "c"]))
+                .add_row(pick(&["1", "2", "3"]))
+                .add_row(pick(&["4", "5", "6"]));
+            assert_eq!(table.select_columns(indices).unwrap(), expected);
+        };
+        select(&[0, 2]);
+        select(&[2, 1, 0]);
+        select(&[1, 1]);
+
+        let none = table.select_columns(&[]).unwrap();
+        assert_eq!(none.column_count(), 0);
+        assert_eq!(none.to_string(), "");
+
+        match table.select_columns(&[0, 3]) {
+            Err(TableError::ColumnIndexOutOfRange { index: 3, len: 3 }) => (),
+            other => panic!("unexpected result: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn rename_header() {
+        let mut table = Table::new();
+        table
+            .add_header(["a_1", "b_2", "c_3"])
+            .add_row(["1", "2", "3"]);
+        table.rename_header(0, "a").unwrap();
+        table.rename_header(2, "c").unwrap();
+        table.rename_header(1, "b (metres)").unwrap();
+        assert_eq!(
+            table.to_string(),
+            "\
++---+------------+---+
+| a | b (metres) | c |
++===+============+===+
+| 1 | 2          | 3 |
++---+------------+---+
+"
+        );
+        match table.rename_header(3, "d") {
+            Err(TableError::ColumnIndexOutOfRange { index: 3, len: 3 }) => (),
+            other => panic!("unexpected result: {:?}", other),
+        }
+
+        let mut table = Table::new();
+        table.add_row(["1"]);
+        match table.rename_header(0, "a") {
+            Err(TableError::NoHeaderRow) => (),
+            other => panic!("unexpected result: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn map_column() {
+        let mut table = Table::new();
+        table
+            .add_header(["name", "n"])
+            .add_row(["ab", "1"])
+            .add_row(["cd", "2.5"])
+            .add_row(["e"]);
+        table
+            .map_column(0, str::to_uppercase)
+            .map_column(1, |cell| format!("{:.2}", cell.parse::<f64>().unwrap()));
+        let mut expected = Table::new();
+        expected
+            .add_header(["name", "n"])
+            .add_row(["AB", "1.00"])
+            .add_row(["CD", "2.50"])
+            .add_row(["E"]);
+        assert_eq!(table, expected);
+
+        let mut table = Table::new();
+        table.add_row(["a", "b"]);
+        assert_eq!(table.to_string(), "+---+---+\n| a | b |\n+---+---+\n");
+        table.map_column(1, |cell| cell.repeat(3));
+        assert_eq!(table.to_string(), "+---+-----+\n| a | bbb |\n+---+-----+\n");
+    }
+
+    #[test]
+    fn add_column() {
+        let mut table = Table::new();
+        table.add_column("id", 1..4).unwrap();
+        table.add_column("name", ["a", "b", "c"]).unwrap();
+        table.add_column("ok", [true, false, true]).unwrap();
+        let mut expected = Table::new();
+        expected
+            .add_header(["id", "name", "ok"])
+            .add_row(["1", "a", "true"])
+            .add_row(["2", "b", "false"])
+            .add_row(["3", "c", "true"]);
+        assert_eq!(table, expected);
+
+        match table.add_column("short", ["x", "y"]) {
+            Err(TableError::IncompatibleRowCounts {
+                expected: 3,
+                got: 2,
+            }) => (),
+            other => panic!("unexpected result: {:?}", other),
+        }
+        assert_eq!(table, expected);
+
+        let mut table = Table::new();
+        table.add_row(["a", "b"]).add_row(["c"]);
+        table.add_column("new", [1, 2]).unwrap();
+        let mut expected = Table::new();
+        expected
+            .add_header(["", "", "new"])
+            .add_row(["a", "b", "1"])
+            .add_row(["c", "", "2"]);
+        assert_eq!(table, expected);
+    }
+
+    #[test]
+    fn pivot() {
+        let mut table = Table::new();
+        table
+            .add_header(["key", "value"])
+            .add_row(["a", "1"])
+            .add_row(["b", "2"])
+            .add_row(["c", "3"]);
+        let mut expected = Table::new();
+        expected
+            .add_header(["a", "b", "c"])
+            .add_row(["1", "2", "3"]);
+        assert_eq!(table.pivot(0, 1, None).unwrap(), expected);
+
+        // the last value for a key wins.
+        table.add_row(["a", "4"]);
+        let mut expected = Table::new();
+        expected
+            .add_header(["a", "b", "c"])
+            .add_row(["4", "2", "3"]);
+        assert_eq!(table.pivot(0, 1, None).unwrap(), expected);
+
+        let mut table = Table::new();
+        table
+            .add_row(["x", "a", "1"])
+            .add_row(["y", "b", "2"])
+            .add_row(["x", "b", "3"])
+            .add_row(["y", "a", "4"])
+            .add_row(["z", "c", "5"]);
+        let mut expected = Table::new();
+        expected
+            .add_header(["", "a", "b", "c"])
+            .add_row(["x", "1", "3", ""])
+            .add_row(["y", "4", "2", ""])
+            .add_row(["z", "", "", "5"]);
+        assert_eq!(table.pivot(1, 2, Some(0)).unwrap(), expected);
+
+        let mut empty = Table::new();
+        empty.add_header(["key", "value"]);
+        assert_eq!(empty.pivot(0, 1, None).unwrap(), Table::new());
+        match empty.pivot(0, 1, Some(2)) {
+            Err(TableError::ColumnIndexOutOfRange { index: 2, len: 2 }) => (),
+            other => panic!("unexpected result: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn freeze_header() {
+        let column = |table: &Table| table.rows.iter().map(|row| &row[0][..]).collect::<String>();
+        let mut table = Table::new();
+        table
+            .add_header(["h"])
+            .add_row(["b"])
+            .add_row(["c"])
+            .add_row(["a"]);
+        assert!(table.is_header_frozen());
+        table.sort_by_column(0, true);
+        assert_eq!(table.header, Some(vec!["h".to_string()]));
+        assert_eq!(column(&table), "abc");
+
+        let mut table = ["h", "b", "c", "a"]
+            .iter()
+            .map(|row| vec![row])
+            .collect::<Table>();
+        assert!(!table.is_header_frozen());
+        table.sort_by_column(0, false);
+        assert_eq!(column(&table), "hcba");
+
+        table.freeze_header();
+        assert!(table.is_header_frozen());
+        table.sort_by_column(0, true);
+        assert_eq!(column(&table), "habc");
+        table.filter_rows(|row| row[0] != "h" && row[0] != "b");
+        assert_eq!(column(&table), "hac");
+        table.map_column(0, str::to_uppercase);
+        assert_eq!(column(&table), "hAC");
+
+        let mut table = ["k", "k", "k"]
+            .iter()
+            .map(|row| vec![row])
+            .collect::<Table>();
+        table.freeze_header().dedup_column(0);
+        assert_eq!(column(&table), "kk");
+        table.filter_rows(|_| false);
+        assert_eq!(column(&table), "k");
+    }
+
+    #[test]
+    fn frozen_header_row_indices() {
+        let column = |table: &Table| table.rows.iter().map(|row| &row[0][..]).collect::<String>();
+        let mut table = ["h", "a", "b", "c"]
+            .iter()
+            .map(|row| vec![row])
+            .collect::<Table>();
+        table.freeze_header();
+        assert_eq!((table.row_count(), table.row_count_with_header()), (3, 4));
+        assert_eq!(table.get_cell(0, 0), Some("a"));
+
+        table.insert_row(0, ["x"]).unwrap();
+        assert_eq!(column(&table), "hxabc");
+        table.insert_row(4, ["y"]).unwrap();
+        assert_eq!(column(&table), "hxabcy");
+        match table.insert_row(6, ["z"]) {
+            Err(TableError::RowOutOfBounds { index: 6, len: 5 }) => (),
+            other => panic!("unexpected result: {:?}", other),
+        }
+
+        table.swap_rows(0, 4).unwrap();
+        assert_eq!(column(&table), "hyabcx");
+        match table.swap_rows(0, 5) {
+            Err(TableError::RowOutOfBounds { index: 5, len: 5 }) => (),
+            other => panic!("unexpected result: {:?}", other),
+        }
+
+        table.remove_row(0).unwrap();
+        assert_eq!(column(&table), "habcx");
+        table.set_cell(3, 0, "d").unwrap();
+        assert_eq!(column(&table), "habcd");
+        table.merge_rows_with(0..2, "").unwrap();
+        assert_eq!(column(&table), "habcd");
+        assert_eq!(table.row_count(), 3);
+
+        let mut other = ["h2", "e"].iter().map(|row| vec![row]).collect::<Table>();
+        other.freeze_header();
+        assert_eq!(column(&table.append(&other).unwrap()), "habcde");
+
+        let mut numbered = table.clone();
+        numbered.number_rows(1);
+        assert_eq!(numbered.rows[0], vec!["#", "h"]);
+        assert_eq!(numbered.rows[1], vec!["1", "ab"]);
+
+        let mut pairs = [["key", "value"], ["a", "1"], ["b", "2"]]
+            .iter()
+            .collect::<Table>();
+        pairs.freeze_header();
+        let mut expected = Table::new();
+        expected.add_header(["a", "b"]).add_row(["1", "2"]);
+        assert_eq!(pairs.pivot(0, 1, None).unwrap(), expected);
+        pairs.add_column("n", [1, 2]).unwrap();
+        assert_eq!(pairs.rows[0], vec!["key", "value", "n"]);
+        assert_eq!(pairs.get_cell(1, 2), Some("2"));
+    }
+
+    #[test]
+    fn dedup_column() {
+        let column = |table: &Table| {
+            table
+                .rows
+                .iter()
+                .map(|row| row.get(1).map_or("-".to_string(), Clone::clone))
+                .collect::<Vec<_>>()
+        };
+        let table = |values: &[&str]| {
+            let mut table = Table::new();
+            table.add_header(["n", "key"]);
+            for (idx, value) in values.iter().enumerate() {
+                table.add_row(vec![idx.to_string(), value.to_string()]);
+            }
+            table
+        };
+
+        let mut unique = table(&["a", "b", "c"]);
+        unique.dedup_column(1);
+        assert_eq!(unique, table(&["a", "b", "c"]));
+
+        let mut run = table(&["a", "a", "a"]);
+        run.dedup_column(1);
+        assert_eq!(column(&run), ["a", "", ""]);
+        assert_eq!(run.header, Some(vec!["n".to_string(), "key".to_string()]));
+
+        let mut runs = table(&["a", "a", "b", "b", "b", "a", "c", "c"]);
+        runs.add_row(["8"]).add_row(["9", "c"]);
+        runs.dedup_column(1);
+        assert_eq!(
+            column(&runs),
+            ["a", "", "b", "", "", "a", "c", "", "-", "c"]
+        );
+    }
+
+    #[test]
+    fn filter_rows() {
+        let table = || {
+            let mut table = Table::new();
+            table
+                .add_header(["id", "name", "team"])
+                .add_row(["1", "a", "red"])
+                .add_row(["2", "b", "blue"])
+                .add_row(["3", "c", "red"]);
+            table
+        };
+        let mut all = table();
+        all.filter_rows(|_| true);
+        assert_eq!(all, table());
+
+        let mut none = table();
+        none.filter_rows(|_| false);
+        let mut expected = Table::new();
+        expected.add_header(["id", "name", "team"]);
+        assert_eq!(none, expected);
+
+        let mut red = table();
+        red.filter_rows(|row| row[2] == "red");
+        expected
+            .add_row(["1", "a", "red"])
+            .add_row(["3", "c", "red"]);
+        assert_eq!(red, expected);
+    }
+
+    #[test]
+    fn merge_rows() {
+        let mut table = Table::new();
+        table
+            .add_header(["name", "tag"])
+            .add_row(["a", "x"])
+            .add_row(["a", "y"])
+            .add_row(["b", "z"])
+            .add_row(["c", "w"]);
+        let mut merged = table.clone();
+        merged.merge_rows(0..2).unwrap();
+        let mut expected = Table::new();
+        expected
+            .add_header(["name", "tag"])
+            .add_row(["a\na", "x\ny"])
+            .add_row(["b", "z"])
+            .add_row(["c", "w"]);
+        assert_eq!(merged, expected);
+        assert_eq!(
+            merged.to_string(),
+            "\
++------+-----+
+| name | tag |
++======+=====+
+| a    | x   |
+| a    | y   |
++------+-----+
+| b    | z   |
++------+-----+
+| c    | w   |
++------+-----+
+"
+        );
+
+        let mut merged = table.clone();
+        merged.merge_rows_with(1..4, "/").unwrap();
+        let mut expected = Table::new();
+        expected
+            .add_header(["name", "tag"])
+            .add_row(["a", "x"])
+            .add_row(["a/b/c", "y/z/w"]);
+        assert_eq!(merged, expected);
+
+        let mut merged = table.clone();
+        merged.merge_rows(2..2).unwrap();
+        assert_eq!(merged, table);
+        merged.merge_rows(3..4).unwrap();
+        assert_eq!(merged, table);
+
+        // identical cells are all kept.
+        let mut merged = table.clone();
+        merged.merge_rows_with(0..1, "/").unwrap();
+        merged.merge_rows_with(0..2, "/").unwrap();
+        assert_eq!(merged.get_cell(0, 0), Some("a/a"));
+
+        let mut merged = table.clone();
+        match merged.merge_rows(3..5) {
+            Err(TableError::RowOutOfBounds { index: 4, len: 4 }) => (),
+            other => panic!("unexpected result: {:?}", other),
+        }
+        assert_eq!(merged, table);
+    }
+
+    #[test]
+    fn sort_by_column() {
+        let mut table = Table::new();
+        table
+            .add_header(["name", "n"])
+            .add_row(["b", "9"])
+            .add_row(["c", "10"])
+            .add_row(["a", "x"])
+            .add_row(["d", "-1.5"]);
+        let column = |table: &Table, col: usize| {
+            table
+                .rows
+                .iter()
+                .map(|row| row[col].clone())
+                .collect::<Vec<_>>()
+        };
+
+        table.sort_by_column(0, true);
+        assert_eq!(column(&table, 0), ["a", "b", "c", "d"]);
+        table.sort_by_column(0, false);
+        assert_eq!(column(&table, 0), ["d", "c", "b", "a"]);
+        assert_eq!(
+            table.header,
+            Some(vec!["name".to_string(), "n".to_string()])
+        );
+
+        table.sort_by_column(1, true);
+        assert_eq!(column(&table, 1), ["-1.5", "10", "9", "x"]);
+        table.sort_by_column_numeric(1, true);
+        assert_eq!(column(&table, 1), ["-1.5", "9", "10", "x"]);
+        table.sort_by_column_numeric(1, false);
+        assert_eq!(column(&table, 1), ["x", "10", "9", "-1.5"]);
+    }
+}
@@ -0,0 +1,373 @@
+//! Measuring how many columns text takes up in a terminal.
+
+use std::borrow::Cow;
+
+/// Get the display width of a single line of text.
+pub(crate) fn str_width(s: &str) -> usize {
+    s.chars().map(char_width).sum()
+}
+
+/// Remove ANSI escape sequences, such as colour codes, from text.
+///
+/// Control sequences (`ESC [` ... final byte) like SGR colours and operating system commands
+/// (`ESC ]` ... `ESC \`) like OSC 8 hyperlinks are removed whole, along with other two-character
+/// escapes. Text without any escapes is borrowed as is.
+pub(crate) fn strip_ansi(s: &str) -> Cow<'_, str> {
+    if !s.contains(ESC) {
+        return Cow::Borrowed(s);
+    }
+    let mut out = String::with_capacity(s.len());
+    let mut rest = s;
+    while let Some(start) = rest.find(ESC) {
+        out.push_str(&rest[..start]);
+        let escape = &rest[start..];
+        rest = &escape[escape_len(escape)..];
+    }
+    out.push_str(rest);
+    Cow::Owned(out)
+}
+
+/// Get the length in bytes of the escape sequence at the start of `s`, which must start with
+/// `ESC`.
+///
+/// A control sequence runs until a final byte in the range `@` to `~`, an operating system
+/// command, like a hyperlink, runs until `BEL` or `ESC \`, and any other escape is a single
+/// character. An unfinished sequence runs to the end of the text.
+fn escape_len(s: &str) -> usize {
+    let mut chars = s.char_indices().skip(1);
+    match chars.next() {
+        Some((_, '[')) => {
+            for (idx, c) in chars {
+                if ('@'..='~').contains(&c) {
+                    return idx + 1;
+                }
+            }
+            s.len()
+        }
+        Some((_, ']')) => {
+            while let Some((idx, c)) = chars.next() {
+                if c == BEL {
+                    return idx + 1;
+                } else if c == ESC {
+                    return chars.next().map_or(s.len(), |(idx, c)| idx + c.len_utf8());
+                }
+            }
+            s.len()
+        }
+        Some((idx, c)) => idx + c.len_utf8(),
+        None => s.len(),
+    }
+}
+
+/// Get the display width of a single line of text, ignoring any ANSI escape sequences.
+pub(crate) fn visible_width(s: &str) -> usize {
+    str_width(&strip_ansi(s))
+}
+
+const ESC: char = '\u{1b}';
+const BEL: char = '\u{7}';
+
+/// The start of an OSC 8 hyperlink.
+pub(crate) const OSC8: &str = "\x1b]8;";
+
+/// A piece of text, as split up by `segments`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub(crate) enum Segment<'a> {
+    /// An ANSI escape sequence, which takes up no space.
+    Escape(&'a str),
+    /// A grapheme cluster.
+    Grapheme(&'a str),
+}
+
+/// Split text into whole ANSI escape sequences and the grapheme clusters between them, so that
+/// text can be cut up without breaking an escape in two.
+pub(crate) fn segments(s: &str) -> Segments<'_> {
+    Segments { rest: s }
+}
+
+/// An iterator over the escapes and grapheme clusters in a string. See `segments`.
+pub(crate) struct Segments<'a> {
+    rest: &'a str,
+}
+
+impl<'a> Iterator for Segments<'a> {
+    type Item = Segment<'a>;
+
+    fn next(&mut self) -> Option<Segment<'a>> {
+        if self.rest.starts_with(ESC) {
+            let (escape, rest) = self.rest.split_at(escape_len(self.rest));
+            self.rest = rest;
+            return Some(Segment::Escape(escape));
+        }
+        let grapheme = graphemes(self.rest).next()?;
+        self.rest = &self.rest[grapheme.len()..];
+        Some(Segment::Grapheme(grapheme))
+    }
+}
+
+/// Split text into grapheme clusters: the units that are displayed as a single character.
+///
+/// With the `unicode` feature, this approximates extended grapheme clusters by keeping
+/// zero-width characters (combining marks, variation selectors and so on) with the character
+/// before them, and joining characters either side of a zero-width joiner. Without it, each
+/// `char` is its own cluster.
+pub(crate) fn graphemes(s: &str) -> Graphemes<'_> {
+    Graphemes { rest: s }
+}
+
+/// An iterator over the grapheme clusters in a string. See `graphemes`.
+pub(crate) struct Graphemes<'a> {
+    rest: &'a str,
+}
+
+impl<'a> Iterator for Graphemes<'a> {
+    type Item = &'a str;
+
+    fn next(&mut self) -> Option<&'a str> {
+        let mut chars = self.rest.char_indices();
+        let (_, first) = chars.next()?;
+        let mut end = first.len_utf8();
+        let mut joined = first == ZWJ;
+        for (idx, c) in chars {
+            if !(joined || continues_cluster(c)) {
+                break;
+            }
+            joined = c == ZWJ;
+            end = idx + c.len_utf8();
+        }
+        let (grapheme, rest) = self.rest.split_at(end);
+        self.rest = rest;
+        Some(grapheme)
+    }
+}
+
+/// The zero-width joiner, used to build up emoji sequences.
+const ZWJ: char = '\u{200d}';
+
+/// Whether the character is part of the same grapheme cluster as the one before it.
+#[cfg(feature = "unicode")]
+fn continues_cluster(c: char) -> bool {
+    c as u32 >= 0x300 && in_table(c as u32, ZERO_WIDTH)
+}
+
+#[cfg(not(feature = "unicode"))]
+fn continues_cluster(_: char) -> bool {
+    false
+}
+
+/// Get the display width of a character.
+///
+/// East Asian wide and fullwidth characters (and most emoji) take 2 columns, combining marks and
+/// other zero-width characters take none, and everything else takes 1.
+#[cfg(feature = "unicode")]
+pub(crate) fn char_width(c: char) -> usize {
+    let c = c as u32;
+    if c < 0x7f {
+        return if c < 0x20 { 0 } else { 1 };
+    }
+    if c < 0xa0 {
+        // C1 control characters
+        return 0;
+    }
+    if in_table(c, ZERO_WIDTH) {
+        0
+    } else if in_table(c, WIDE) {
+        2
+    } else {
+        1
+    }
+}
+
+/// Get the display width of a character.
+///
+/// Without the `unicode` feature every character is assumed to take a single column.
+#[cfg(not(feature = "unicode"))]
+pub(crate) fn char_width(_: char) -> usize {
+    1
+}
+
+#[cfg(feature = "unicode")]
+fn in_table(c: u32, table: &[(u32, u32)]) -> bool {
+    table
+        .binary_search_by(|&(start, end)| {
+            if end < c {
+                ::std::cmp::Ordering::Less
+            } else if start > c {
+                ::std::cmp::Ordering::Greater
+            } else {
+                ::std::cmp::Ordering::Equal
+            }
+        })
+        .is_ok()
+}
+
+/// Inclusive ranges of combining marks, format characters and other characters that take up no
+/// space.
+#[cfg(feature = "unicode")]
+const ZERO_WIDTH: &[(u32, u32)] = &[
+    (0x0300, 0x036f),
+    (0x0483, 0x0489),
+    (0x0591, 0x05bd),
+    (0x05bf, 0x05bf),
+    (0x05c1, 0x05c2),
+    (0x05c4, 0x05c5),
+    (0x05c7, 0x05c7),
+    (0x0610, 0x061a),
+    (0x064b, 0x065f),
+    (0x0670, 0x0670),
+    (0x06d6, 0x06dc),
+    (0x06df, 0x06e4),
+    (0x06e7, 0x06e8),
+    (0x06ea, 0x06ed),
+    (0x0e31, 0x0e31),
+    (0x0e34, 0x0e3a),
+    (0x0e47, 0x0e4e),
+    (0x1ab0, 0x1aff),
+    (0x1dc0, 0x1dff),
+    (0x200b, 0x200f),
+    (0x2028, 0x202e),
+    (0x2060, 0x2064),
+    (0x20d0, 0x20ff),
+    (0xfe00, 0xfe0f),
+    (0xfe20, 0xfe2f),
+    (0xfeff, 0xfeff),
+    (0xe0000, 0xe007f),
+    (0xe0100, 0xe01ef),
+];
+
+/// Inclusive ranges of characters that take up two columns.
+#[cfg(feature = "unicode")]
+const WIDE: &[(u32, u32)] = &[
+    (0x1100, 0x115f),
+    (0x231a, 0x231b),
+    (0x2329, 0x232a),
+    (0x23e9, 0x23ec),
+    (0x23f0, 0x23f0),
+    (0x23f3, 0x23f3),
+    (0x25fd, 0x25fe),
+    (0x2614, 0x2615),
+    (0x2648, 0x2653),
+    (0x267f, 0x267f),
+    (0x2693, 0x2693),
+    (0x26a1, 0x26a1),
+    (0x26aa, 0x26ab),
+    (0x26bd, 0x26be),
+    (0x26c4, 0x26c5),
+    (0x26ce, 0x26ce),
+    (0x26d4, 0x26d4),
+    (0x26ea, 0x26ea),
+    (0x26f2, 0x26f3),
+    (0x26f5, 0x26f5),
+    (0x26fa, 0x26fa),
+    (0x26fd, 0x26fd),
+    (0x2705, 0x2705),
+    (0x270a, 0x270b),
+    (0x2728, 0x2728),
+    (0x274c, 0x274c),
+    (0x274e, 0x274e),
+    (0x2753, 0x2755),
+    (0x2757, 0x2757),
+    (0x2795, 0x2797),
+    (0x27b0, 0x27b0),
+    (0x27bf, 0x27bf),
+    (0x2b1b, 0x2b1c),
+    (0x2b50, 0x2b50),
+    (0x2b55, 0x2b55),
+    (0x2e80, 0x303e),
+    (0x3041, 0x33ff),
+    (0x3400, 0x4dbf),
+    (0x4e00, 0x9fff),
+    (0xa000, 0xa4cf),
+    (0xa960, 0xa97f),
+    (0xac00, 0xd7a3),
+    (0xf900, 0xfaff),
+    (0xfe10, 0xfe19),
+    (0xfe30, 0xfe6f),
+    (0xff00, 0xff60),
+    (0xffe0, 0xffe6),
+    (0x16fe0, 0x16fe4),
+    (0x17000, 0x18aff),
+    (0x1b000, 0x1b2ff),
+    (0x1f004, 0x1f004),
+    (0x1f0cf, 0x1f0cf),
+    (0x1f18e, 0x1f18e),
+    (0x1f191, 0x1f19a),
+    (0x1f200, 0x1f251),
+    (0x1f300, 0x1f64f),
+    (0x1f680, 0x1f6ff),
+    (0x1f7e0, 0x1f7eb),
+    (0x1f90c, 0x1f9ff),
+    (0x1fa70, 0x1faff),
+    (0x20000, 0x2fffd),
+    (0x30000, 0x3fffd),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::str_width;
+
+    #[test]
+    fn ascii_width() {
+        assert_eq!(str_width(""), 0);
+        assert_eq!(str_width("hello"), 5);
+    }
+
+    #[cfg(feature = "unicode")]
+    #[test]
+    fn unicode_width() {
+        assert_eq!(str_width("中文"), 4);
+        assert_eq!(str_width("a中b"), 4);
+        assert_eq!(str_width("e\u{301}"), 1);
+        assert_eq!(str_width("한국어"), 6);
+        assert_eq!(str_width("😀"), 2);
+        assert_eq!(str_width("ünïcödé"), 7);
+    }
+
+    #[cfg(feature = "unicode")]
+    #[test]
+    fn grapheme_clusters() {
+        let clusters = |s| super::graphemes(s).collect::<Vec<_>>();
+        assert_eq!(clusters("abc"), vec!["a", "b", "c"]);
+        assert_eq!(clusters("e\u{301}x"), vec!["e\u{301}", "x"]);
+        assert_eq!(clusters("中文"), vec!["中", "文"]);
+        assert_eq!(clusters("👩\u{200d}👧!"), vec!["👩\u{200d}👧", "!"]);
+        assert!(clusters("").is_empty());
+    }
+
+    #[test]
+    fn strip_ansi() {
+        let tests = vec![
+            ("plain", "plain"),
+            ("\x1b[31mred\x1b[0m", "red"),
+            ("\x1b[1;38;5;208mbold\x1b[m text", "bold text"),
+            ("a\x1b", "a"),
+            ("a\x1b[1;3", "a"),
+            ("\x1b]8;;http://a.b\x1b\\link\x1b]8;;\x1b\\!", "link!"),
+            ("\x1b]0;title\x07text", "text"),
+        ];
+        for (text, result) in tests {
+            assert_eq!(super::strip_ansi(text), result);
+        }
+    }
+
+    #[test]
+    fn ansi_segments() {
+        use super::Segment::{Escape, Grapheme};
+
+        let segments = |s| super::segments(s).collect::<Vec<_>>();
+        assert_eq!(
+            segments("\x1b[31mab\x1b[0m"),
+            vec![
+                Escape("\x1b[31m"),
+                Grapheme("a"),
+                Grapheme("b"),
+                Escape("\x1b[0m")
+            ]
+        );
+        assert_eq!(
+            segments("\x1b]8;;u\x1b\\x\x1b[1"),
+            vec![Escape("\x1b]8;;u\x1b\\"), Grapheme("x"), Escape("\x1b[1")]
+        );
+        assert!(segments("").is_empty());
+    }
+}
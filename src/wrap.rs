@@ -0,0 +1,295 @@
+//! Fitting cell content into a maximum width.
+
+use width::{segments, str_width, visible_width, Segment, OSC8};
+
+/// Wrap text so that no line is wider than `max_width`.
+///
+/// Lines are broken at whitespace where possible. Words that are too long to fit on a line of
+/// their own are broken wherever they need to be. Lines that already fit are left untouched.
+///
+/// ANSI escape sequences take up no space and are never split. Colours and hyperlinks that are
+/// still open at the end of a line are closed there and opened again at the start of the next, so
+/// they don't spill onto the borders.
+pub(crate) fn wrap(text: &str, max_width: usize) -> String {
+    // a width of 0 would never make progress.
+    let max_width = if max_width == 0 { 1 } else { max_width };
+    let mut out = Vec::new();
+    for line in text.lines() {
+        if visible_width(line) <= max_width {
+            out.push(line.to_string());
+            continue;
+        }
+        let mut lines = Vec::new();
+        let mut current = String::new();
+        let mut current_width = 0;
+        for word in line.split_whitespace() {
+            let word_width = visible_width(word);
+            if word_width == 0 {
+                // only escapes, so there's nothing to separate.
+                current.push_str(word);
+                continue;
+            }
+            if current_width > 0 && current_width + 1 + word_width <= max_width {
+                current.push(' ');
+                current.push_str(word);
+                current_width += 1 + word_width;
+                continue;
+            }
+            if current_width > 0 {
+                lines.push(current.split_off(0));
+                current_width = 0;
+            }
+            if word_width <= max_width {
+                current.push_str(word);
+                current_width = word_width;
+                continue;
+            }
+            // the word is too long, so hard-break it.
+            for segment in segments(word) {
+                let grapheme = match segment {
+                    Segment::Escape(escape) => {
+                        current.push_str(escape);
+                        continue;
+                    }
+                    Segment::Grapheme(grapheme) => grapheme,
+                };
+                let width = str_width(grapheme);
+                if current_width > 0 && current_width + width > max_width {
+                    lines.push(current.split_off(0));
+                    current_width = 0;
+                }
+                current.push_str(grapheme);
+                current_width += width;
+            }
+        }
+        lines.push(current);
+        let mut state = EscapeState::default();
+        for line in lines {
+            let mut wrapped = String::new();
+            state.reopen(&mut wrapped);
+            wrapped.push_str(&line);
+            state.update(&line);
+            state.close(&mut wrapped);
+            out.push(wrapped);
+        }
+    }
+    out.join("\n")
+}
+
+/// Truncate each line of text so it is no wider than `max_width`, ending shortened lines with
+/// `marker`.
+///
+/// Text is only ever cut between grapheme clusters, so accented and combined characters are kept
+/// whole. If the marker itself doesn't fit, the text is cut without it. ANSI escape sequences take
+/// up no space and are kept whole, even after the cut, so hyperlinks keep their closing sequence,
+/// and any colour still set is reset before the marker.
+pub(crate) fn truncate(text: &str, max_width: usize, marker: &str) -> String {
+    let marker_width = str_width(marker);
+    let mut out = Vec::new();
+    for line in text.lines() {
+        if visible_width(line) <= max_width {
+            out.push(line.to_string());
+            continue;
+        }
+        let (budget, marker) = if marker_width < max_width {
+            (max_width - marker_width, marker)
+        } else {
+            (max_width, "")
+        };
+        let mut current = String::new();
+        let mut current_width = 0;
+        let mut full = false;
+        for segment in segments(line) {
+            match segment {
+                Segment::Escape(escape) => current.push_str(escape),
+                Segment::Grapheme(_) if full => (),
+                Segment::Grapheme(grapheme) => {
+                    let width = str_width(grapheme);
+                    if current_width + width > budget {
+                        full = true;
+                        continue;
+                    }
+                    current.push_str(grapheme);
+                    current_width += width;
+                }
+            }
+        }
+        let mut state = EscapeState::default();
+        state.update(&current);
+        state.close(&mut current);
+        current.push_str(marker);
+        out.push(current);
+    }
+    out.join("\n")
+}
+
+/// The sequence that closes an OSC 8 hyperlink.
+const LINK_END: &str = "\x1b]8;;\x1b\\";
+
+/// The escape sequences that are in effect at some point in a line of text.
+#[derive(Debug, Default)]
+struct EscapeState {
+    /// The colours and other graphic renditions set since the last reset.
+    graphics: Vec<String>,
+    /// The start of the OSC 8 hyperlink that is open, if there is one.
+    link: Option<String>,
+}
+
+impl EscapeState {
+    /// Follow the escape sequences in `text`.
+    fn update(&mut self, text: &str) {
+        for segment in segments(text) {
+            let escape = match segment {
+                Segment::Escape(escape) => escape,
+                Segment::Grapheme(_) => continue,
+            };
+            if escape.starts_with("\x1b[") && escape.ends_with('m') {
+                match &escape[2..escape.len() - 1] {
+                    "" | "0" => self.graphics.clear(),
+                    _ => self.graphics.push(escape.to_string()),
+                }
+            } else if let Some(params) = escape.strip_prefix(OSC8) {
+                // a link with no URL closes the one that is open.
+                let params = params.trim_end_matches(['\x1b', '\\', '\x07']);
+                self.link = if params.ends_with(';') {
+                    None
+                } else {
+                    Some(escape.to_string())
+                };
+            }
+        }
+    }
+
+    /// Write out the escapes needed to get back into this state.
+    fn reopen(&self, out: &mut String) {
+        for escape in &self.graphics {
+            out.push_str(escape);
+        }
+        if let Some(ref link) = self.link {
+            out.push_str(link);
+        }
+    }
+
+    /// Write out the escapes needed to get back to plain text.
+    fn close(&self, out: &mut String) {
+        if self.link.is_some() {
+            out.push_str(LINK_END);
+        }
+        if !self.graphics.is_empty() {
+            out.push_str("\x1b[0m");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{truncate, wrap};
+
+    #[test]
+    fn wrap_words() {
+        let tests = vec![
+            ("short", 10, "short"),
+            ("", 10, ""),
+            ("aaaaa bbbb cccc dddd", 10, "aaaaa bbbb\ncccc dddd"),
+            ("one two three", 3, "one\ntwo\nthr\nee"),
+            ("abcdefghij klm", 4, "abcd\nefgh\nij\nklm"),
+            ("fits\nthis doesn't", 5, "fits\nthis\ndoesn\n't"),
+            ("ab", 0, "a\nb"),
+        ];
+        for (text, width, result) in tests {
+            assert_eq!(wrap(text, width), result, "{:?} at {}", text, width);
+        }
+    }
+
+    #[test]
+    fn truncate_text() {
+        let tests = vec![
+            ("exactly10!", 10, "…", "exactly10!"),
+            ("hello world", 8, "…", "hello w…"),
+            ("hello world", 8, "...", "hello..."),
+            ("hello", 2, "...", "he"),
+            ("one\ntwo three", 4, "…", "one\ntwo…"),
+        ];
+        for (text, width, marker, result) in tests {
+            assert_eq!(
+                truncate(text, width, marker),
+                result,
+                "{:?} at {}",
+                text,
+                width
+            );
+        }
+    }
+
+    #[cfg(feature = "unicode")]
+    #[test]
+    fn truncate_unicode() {
+        // wide characters are never split, so the result can be narrower than the max.
+        assert_eq!(truncate("中文字符", 5, "…"), "中文…");
+        assert_eq!(truncate("中文字符", 6, "…"), "中文…");
+        assert_eq!(truncate("中文字符", 8, "…"), "中文字符");
+        // combining accents stay with their letter.
+        assert_eq!(truncate("e\u{301}e\u{301}e\u{301}", 2, "…"), "e\u{301}…");
+    }
+
+    #[test]
+    fn wrap_ansi() {
+        let tests = vec![
+            (
+                "\x1b[31mred red red\x1b[0m",
+                4,
+                "\x1b[31mred\x1b[0m\n\x1b[31mred\x1b[0m\n\x1b[31mred\x1b[0m",
+            ),
+            ("\x1b[1mab\x1b[0m", 2, "\x1b[1mab\x1b[0m"),
+            (
+                "\x1b[32mabcdef\x1b[0m",
+                3,
+                "\x1b[32mabc\x1b[0m\n\x1b[32mdef\x1b[0m",
+            ),
+            ("a \x1b[0m", 1, "a\x1b[0m"),
+        ];
+        for (text, width, result) in tests {
+            assert_eq!(wrap(text, width), result, "{:?} at {}", text, width);
+        }
+    }
+
+    #[test]
+    fn wrap_hyperlink() {
+        let link = "\x1b]8;;http://a.b\x1b\\one two\x1b]8;;\x1b\\";
+        assert_eq!(
+            wrap(link, 4),
+            "\x1b]8;;http://a.b\x1b\\one\x1b]8;;\x1b\\\n\x1b]8;;http://a.b\x1b\\two\x1b]8;;\x1b\\"
+        );
+    }
+
+    #[test]
+    fn truncate_ansi() {
+        let tests = vec![
+            ("\x1b[31mred red\x1b[0m", 4, "\x1b[31mred\x1b[0m…"),
+            ("\x1b[31mred red", 4, "\x1b[31mred\x1b[0m…"),
+            ("\x1b[31mred\x1b[0m", 3, "\x1b[31mred\x1b[0m"),
+            // escapes after the cut are kept, so nothing is left open.
+            ("ab\x1b[1mcd\x1b[m", 3, "ab\x1b[1m\x1b[m…"),
+            (
+                "\x1b]8;;http://a.b\x1b\\link\x1b]8;;\x1b\\",
+                3,
+                "\x1b]8;;http://a.b\x1b\\li\x1b]8;;\x1b\\…",
+            ),
+            // an unterminated link is closed before the marker.
+            (
+                "\x1b]8;;http://a.b\x1b\\link",
+                3,
+                "\x1b]8;;http://a.b\x1b\\li\x1b]8;;\x1b\\…",
+            ),
+        ];
+        for (text, width, result) in tests {
+            assert_eq!(
+                truncate(text, width, "…"),
+                result,
+                "{:?} at {}",
+                text,
+                width
+            );
+        }
+    }
+}
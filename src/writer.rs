@@ -0,0 +1,285 @@
+//! Writing a table a row at a time.
+
+use std::{cmp, fmt::Display, io};
+
+use style::BorderLine;
+use {cell_width, render_border_line, render_text_line, write_io, Options, TableError};
+
+/// Writes a table row by row, without collecting all the data first.
+///
+/// Because nothing is buffered, the width of each column must be known up front. Cells wider
+/// than their column are written in full, which will break the border alignment.
+///
+/// # Example
+///
+/// ```
+/// use text_tables::TableWriter;
+///
+/// let mut out = Vec::new();
+/// {
+///     let mut writer = TableWriter::new(&mut out, &[5, 3]);
+///     writer.write_border().unwrap();
+///     for i in 0..3 {
+///         if i > 0 {
+///             writer.write_border().unwrap();
+///         }
+///         writer.write_row(&[format!("row {}", i), i.to_string()]).unwrap();
+///     }
+///     writer.write_bottom_border().unwrap();
+/// }
+/// println!("{}", ::std::str::from_utf8(&out).unwrap());
+/// ```
+#[derive(Debug)]
+pub struct TableWriter<W> {
+    writer: W,
+    widths: Vec<usize>,
+    options: Options,
+    rows_written: usize,
+}
+
+impl<W: io::Write> TableWriter<W> {
+    /// Create a writer for a table whose columns have the given widths.
+    pub fn new(writer: W, widths: &[usize]) -> Self {
+        TableWriter::with_options(writer, widths, &Options::default())
+    }
+
+    /// Create a writer for a table whose columns have the given widths, using the given options
+    /// for the style, padding and alignment.
+    ///
+    /// Measure the widths with a `WidthAccumulator` made with the same options, so that things
+    /// like `strip_ansi_for_width` are taken into account.
+    pub fn with_options(writer: W, widths: &[usize], options: &Options) -> Self {
+        TableWriter {
+            writer,
+            widths: widths.to_vec(),
+            options: options.clone(),
+            rows_written: 0,
+        }
+    }
+
+    /// Write a horizontal border line.
+    ///
+    /// Before any rows this is the top edge of the table, and after that it is a line between
+    /// rows. Use `write_bottom_border` for the line at the end.
+    pub fn write_border(&mut self) -> Result<(), TableError> {
+        let line = if self.rows_written == 0 {
+            BorderLine::Top
+        } else {
+            BorderLine::Middle
+        };
+        self.write_border_line(line)
+    }
+
+    /// Write the bottom edge of the table, which closes it off.
+    pub fn write_bottom_border(&mut self) -> Result<(), TableError> {
+        self.write_border_line(BorderLine::Bottom)
+    }
+
+    /// Write a horizontal border line with the junctions for `line`.
+    fn write_border_line(&mut self, line: BorderLine) -> Result<(), TableError> {
+        let widths = &self.widths;
+        let options = &self.options;
+        write_io(&mut self.writer, |writer| {
+            render_border_line(writer, widths, options, options.style.horizontal, line)
+                .map_err(From::from)
+        })
+    }
+
+    /// Write a row of cells.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the row does not have one cell per column, or if writing fails.
+    pub fn write_row<C: Display>(&mut self, row: &[C]) -> Result<(), TableError> {
+        if row.len() != self.widths.len() {
+            return Err(TableError::UnequalRowLengths {
+                expected: self.widths.len(),
+                got: row.len(),
+                row_index: self.rows_written,
+            });
+        }
+        let row = row.iter().map(|cell| cell.to_string()).collect::<Vec<_>>();
+        let widths = &self.widths;
+        let options = &self.options;
+        write_io(&mut self.writer, |writer| {
+            render_text_line(writer, widths, options, &row).map_err(From::from)
+        })?;
+        self.rows_written += 1;
+        Ok(())
+    }
+
+    /// Get back the underlying writer.
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+/// Works out the width of each column one row at a time, for use with `TableWriter`.
+///
+/// This lets a table that is too big to hold in memory be written in two passes over its data:
+/// one to measure the columns, and one to write the rows. The widths are the same as the ones
+/// `column_widths` gives. Use `with_options` here and for the `TableWriter` when rendering with
+/// options that change how cells are measured.
+///
+/// ```
+/// use text_tables::{TableWriter, WidthAccumulator};
+///
+/// let data = [["a", "bcd"], ["ef", "g"]];
+/// let mut widths = WidthAccumulator::new(2);
+/// for row in data.iter() {
+///     widths.observe_row(row);
+/// }
+/// let widths = widths.finish();
+/// assert_eq!(widths, [2, 3]);
+///
+/// let mut writer = TableWriter::new(Vec::new(), &widths);
+/// writer.write_border().unwrap();
+/// for (idx, row) in data.iter().enumerate() {
+///     if idx > 0 {
+///         writer.write_border().unwrap();
+///     }
+///     writer.write_row(row).unwrap();
+/// }
+/// writer.write_bottom_border().unwrap();
+/// ```
+#[derive(Debug, Clone)]
+pub struct WidthAccumulator {
+    widths: Vec<usize>,
+    options: Options,
+}
+
+impl WidthAccumulator {
+    /// Start measuring a table with `n_cols` columns.
+    pub fn new(n_cols: usize) -> Self {
+        WidthAccumulator::with_options(n_cols, &Options::default())
+    }
+
+    /// Start measuring a table with `n_cols` columns, measuring cells the way they are measured
+    /// when rendering with `options`.
+    pub fn with_options(n_cols: usize, options: &Options) -> Self {
+        WidthAccumulator {
+            widths: vec![0; n_cols],
+            options: options.clone(),
+        }
+    }
+
+    /// Widen the columns to fit the cells of `row`. A row with more cells than there are columns
+    /// adds columns for the extra cells.
+    pub fn observe_row<C: Display>(&mut self, row: &[C]) {
+        if row.len() > self.widths.len() {
+            self.widths.resize(row.len(), 0);
+        }
+        for (width, cell) in self.widths.iter_mut().zip(row) {
+            *width = cmp::max(*width, cell_width(&cell.to_string(), &self.options));
+        }
+    }
+
+    /// Get the width of each column.
+    pub fn finish(self) -> Vec<usize> {
+        self.widths
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{TableWriter, WidthAccumulator};
+
+    #[test]
+    fn matches_render() {
+        let data = [["single", "line", "a"], ["second", "lines", "a"]];
+        let mut writer = TableWriter::new(Vec::new(), &[6, 5, 1]);
+        writer.write_border().unwrap();
+        for row in data.iter() {
+            writer.write_row(row).unwrap();
+            writer.write_border().unwrap();
+        }
+        assert_eq!(writer.into_inner(), ::render_to_string(data).into_bytes());
+    }
+
+    #[test]
+    fn bottom_border() {
+        let data = [["a", "bcd"], ["ef", "g"]];
+        let options = ::Options {
+            style: ::Style::from(::BoxStyle::Unicode),
+            ..::Options::default()
+        };
+        let mut writer = TableWriter::with_options(Vec::new(), &[2, 3], &options);
+        writer.write_border().unwrap();
+        for (idx, row) in data.iter().enumerate() {
+            if idx > 0 {
+                writer.write_border().unwrap();
+            }
+            writer.write_row(row).unwrap();
+        }
+        writer.write_bottom_border().unwrap();
+        let mut rendered = Vec::new();
+        ::render_with_options(&mut rendered, data, &options).unwrap();
+        assert_eq!(
+            String::from_utf8(writer.into_inner()).unwrap(),
+            String::from_utf8(rendered).unwrap()
+        );
+    }
+
+    #[test]
+    fn row_length() {
+        let mut writer = TableWriter::new(Vec::new(), &[1, 1]);
+        writer.write_row(&["a", "b"]).unwrap();
+        match writer.write_row(&["c"]) {
+            Err(::TableError::UnequalRowLengths {
+                expected: 2,
+                got: 1,
+                row_index: 1,
+            }) => (),
+            other => panic!("unexpected result {:?}", other),
+        }
+    }
+
+    #[test]
+    fn width_accumulator() {
+        let tables = vec![
+            vec![],
+            vec![vec!["a", ""]],
+            vec![vec!["two\nlines", "b"], vec!["c", "二二"], vec!["", "d"]],
+        ];
+        for data in &tables {
+            let mut widths = WidthAccumulator::new(data.first().map_or(0, Vec::len));
+            for row in data {
+                widths.observe_row(row);
+            }
+            assert_eq!(widths.finish(), ::column_widths(data), "{:?}", data);
+        }
+
+        let mut widths = WidthAccumulator::new(1);
+        widths.observe_row(&["ab", "c"]);
+        assert_eq!(widths.finish(), [2, 1]);
+    }
+
+    #[test]
+    fn with_options() {
+        let data = [["\x1b[31mred\x1b[0m", "a"], ["plain", "b"]];
+        let options = ::Options {
+            strip_ansi_for_width: true,
+            style: ::Style::from(::BoxStyle::Rounded),
+            ..::Options::default()
+        };
+        let mut widths = WidthAccumulator::with_options(2, &options);
+        for row in data.iter() {
+            widths.observe_row(row);
+        }
+        let widths = widths.finish();
+        assert_eq!(widths, [5, 1]);
+
+        let mut writer = TableWriter::with_options(Vec::new(), &widths, &options);
+        writer.write_border().unwrap();
+        for (idx, row) in data.iter().enumerate() {
+            if idx > 0 {
+                writer.write_border().unwrap();
+            }
+            writer.write_row(row).unwrap();
+        }
+        writer.write_bottom_border().unwrap();
+        let mut rendered = Vec::new();
+        ::render_with_options(&mut rendered, data, &options).unwrap();
+        assert_eq!(writer.into_inner(), rendered);
+    }
+}